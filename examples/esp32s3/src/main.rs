@@ -13,7 +13,7 @@ use esp_hal::i2c::master::Config;
 use esp_hal::i2c::master::I2c;
 use esp_hal::timer::systimer::SystemTimer;
 use esp_hal::Async;
-use mpr121_hal::mpr121::Mpr121;
+use mpr121_hal::mpr121::asynch::Mpr121;
 use mpr121_hal::Mpr121Address;
 use panic_rtt_target as _;
 use static_cell::StaticCell;
@@ -50,7 +50,7 @@ async fn main(spawner: Spawner) {
         ))
     };
     let i2c = I2cDevice::new(i2c_bus);
-    let mut mpr121 = Mpr121::new(i2c, Mpr121Address::Default, &mut embassy_time::Delay, true)
+    let mut mpr121 = Mpr121::new(i2c, Mpr121Address::Default, &mut embassy_time::Delay)
         .await
         .expect("Failed to initialize MPR121");
 
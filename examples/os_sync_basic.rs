@@ -1,12 +1,12 @@
 use ftdi::Device;
 use ftdi_embedded_hal::Delay;
 use ftdi_embedded_hal::{self as hal, I2c};
-use mpr121_hal::mpr121::Mpr121;
+use mpr121_hal::mpr121::blocking::Mpr121;
 use mpr121_hal::Channel;
 use std::error::Error;
 
-#[cfg(feature = "async")]
-compile_error!("You cant run this example in async mode. Try setting the sync feature");
+#[cfg(not(feature = "sync"))]
+compile_error!("This example uses the blocking driver. Enable the sync feature to run it");
 
 fn main() {
     // This is a placeholder for the main function.
@@ -18,7 +18,6 @@ fn main() {
         i2c_bus,
         mpr121_hal::Mpr121Address::Default,
         &mut Delay::new(),
-        true,
     )
     .unwrap();
     loop {
@@ -34,13 +33,11 @@ fn main() {
         // Wait as to not spam the console
         std::thread::sleep(std::time::Duration::from_millis(1000));
         println!("Calling get_snesor_touch! (individual touch channels");
-        for selected in 0..Channel::NUM_CHANNELS {
+        for selected in Channel::iter() {
             println!(
                 "Channel: {:?} : Touch Status: {:?}",
                 selected,
-                mpr121
-                    .get_sensor_touch(Channel::try_from(selected).unwrap())
-                    .unwrap(),
+                mpr121.get_sensor_touch(selected).unwrap(),
             );
         }
         // Add a delay to avoid flooding the output
@@ -0,0 +1,579 @@
+//! Typed configuration of the sensor, which is applied when the driver is created with `Mpr121::with_config`, see the [mpr121](crate::mpr121) module.
+//!
+//! The [Default] configuration mirrors the [Adafruit implementation](https://github.com/adafruit/Adafruit_MPR121), except for the threshold values.
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::{
+    registers::{
+        afe, ecr, limits, Register, ELECTRODE_FILTER_REGISTERS, PROXIMITY_FILTER_REGISTERS,
+    },
+    BaselineFilter, CalibrationLock, Channel, ChargeCurrent, ChargeTime, DebounceNumber, Electrode,
+    ElectrodeSampleInterval, FilterRates, FirstFilterIterations, ProximityMode,
+    SecondFilterIterations, TouchedFilterRates,
+};
+
+/// The number of electrodes with their own charge settings, the 12 touch electrodes and ELEPROX
+const ELECTRODE_COUNT: usize = Channel::NUM_CHANNELS as usize + 1;
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// This enum represents how often a failed auto-configuration is retried (RETRY)
+pub enum AutoConfigRetry {
+    NoRetry,
+    Twice,
+    FourTimes,
+    EightTimes,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// This enum represents how the baseline is set after a successful auto-configuration (BVA).
+/// The datasheet recommends to match this with the [CalibrationLock].
+pub enum BaselineValueAdjust {
+    /// The baseline is not changed
+    Unchanged,
+    /// The baseline is cleared
+    Cleared,
+    /// The baseline is set to the auto-configuration result with the lower 3 bits cleared
+    Set5Bit,
+    /// The baseline is set to the auto-configuration result
+    Set10Bit,
+}
+
+/// The auto-configuration of the charge current and charge time of every enabled electrode. See section 5.12 in the
+/// [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf) and [note AN3889](https://www.nxp.com/docs/en/application-note/AN3889.pdf).
+///
+/// The `Default` value mirrors the Adafruit implementation, which enables auto-configuration and auto-reconfiguration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AutoConfig {
+    /// First filter iterations (FFI). The device requires this to match the global first filter iterations, so applying
+    /// the auto-configuration keeps the global ones configured with `Mpr121Config::with_filter_iterations` or `Mpr121::set_filter_iterations`.
+    pub first_filter_iterations: FirstFilterIterations,
+    /// Number of retries of a failed auto-configuration (RETRY)
+    pub retry: AutoConfigRetry,
+    /// Baseline value adjust (BVA)
+    pub baseline_value_adjust: BaselineValueAdjust,
+    /// Auto-reconfiguration enabled (ARE), reruns the configuration whenever an electrode is out of range
+    pub auto_reconfig: bool,
+    /// Auto-configuration enabled (ACE), runs the configuration on every transition from STOP to RUN mode
+    pub auto_config: bool,
+    /// Skip charge time search (SCTS), only the charge current is configured
+    pub skip_charge_time_search: bool,
+    /// Raise the IRQ line when an electrode is out of range (OORIE)
+    pub out_of_range_interrupt: bool,
+    /// Raise the IRQ line when the auto-reconfiguration fails (ARFIE)
+    pub auto_reconfig_fail_interrupt: bool,
+    /// Raise the IRQ line when the auto-configuration fails (ACFIE)
+    pub auto_config_fail_interrupt: bool,
+    /// Up-side limit (USL) of the electrode data
+    pub up_side_limit: u8,
+    /// Low-side limit (LSL) of the electrode data
+    pub low_side_limit: u8,
+    /// Target level (TL) of the electrode data
+    pub target_level: u8,
+}
+
+impl Default for AutoConfig {
+    fn default() -> Self {
+        AutoConfig {
+            first_filter_iterations: FirstFilterIterations::Samples6,
+            retry: AutoConfigRetry::NoRetry,
+            baseline_value_adjust: BaselineValueAdjust::Set5Bit,
+            auto_reconfig: true,
+            auto_config: true,
+            skip_charge_time_search: false,
+            out_of_range_interrupt: false,
+            auto_reconfig_fail_interrupt: false,
+            auto_config_fail_interrupt: false,
+            up_side_limit: limits::UP_SIDE,
+            low_side_limit: limits::LOW_SIDE,
+            target_level: limits::TARGET_LEVEL,
+        }
+    }
+}
+
+impl AutoConfig {
+    /// Returns the default settings with auto-configuration and auto-reconfiguration disabled
+    pub fn disabled() -> Self {
+        AutoConfig {
+            auto_reconfig: false,
+            auto_config: false,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the values of the AutoConfig0 and AutoConfig1 registers. See Datasheet 5.12
+    pub(crate) fn register_values(&self) -> [u8; 2] {
+        let control_0 = (u8::from(self.first_filter_iterations) << 6)
+            | (u8::from(self.retry) << 4)
+            | (u8::from(self.baseline_value_adjust) << 2)
+            | (u8::from(self.auto_reconfig) << 1)
+            | u8::from(self.auto_config);
+        let control_1 = (u8::from(self.skip_charge_time_search) << 7)
+            | (u8::from(self.out_of_range_interrupt) << 2)
+            | (u8::from(self.auto_reconfig_fail_interrupt) << 1)
+            | u8::from(self.auto_config_fail_interrupt);
+        [control_0, control_1]
+    }
+
+    /// Decodes the AutoConfig0, AutoConfig1, USL, LSL and TL registers. See Datasheet 5.12
+    pub(crate) fn from_register_values(values: [u8; 5]) -> Self {
+        let [control_0, control_1, up_side_limit, low_side_limit, target_level] = values;
+        AutoConfig {
+            first_filter_iterations: FirstFilterIterations::try_from(control_0 >> 6)
+                .expect("Every two bit value is a valid first filter iterations"),
+            retry: AutoConfigRetry::try_from((control_0 >> 4) & 0b11)
+                .expect("Every two bit value is a valid retry"),
+            baseline_value_adjust: BaselineValueAdjust::try_from((control_0 >> 2) & 0b11)
+                .expect("Every two bit value is a valid baseline value adjust"),
+            auto_reconfig: (control_0 & 0b10) != 0,
+            auto_config: (control_0 & 0b1) != 0,
+            skip_charge_time_search: (control_1 & 0b1000_0000) != 0,
+            out_of_range_interrupt: (control_1 & 0b100) != 0,
+            auto_reconfig_fail_interrupt: (control_1 & 0b10) != 0,
+            auto_config_fail_interrupt: (control_1 & 0b1) != 0,
+            up_side_limit,
+            low_side_limit,
+            target_level,
+        }
+    }
+}
+
+/// The configuration of the sensor. Use the `with_*` methods to change single settings of the [Default] configuration:
+///
+/// ```
+/// use mpr121_hal::config::Mpr121Config;
+/// use mpr121_hal::DebounceNumber;
+///
+/// let config = Mpr121Config::default()
+///     .with_thresholds(20, 10)
+///     .with_debounce(DebounceNumber::Two, DebounceNumber::One)
+///     .with_electrode_count(4);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Mpr121Config {
+    electrode_filter: BaselineFilter,
+    proximity_filter: BaselineFilter,
+    thresholds: [(u8, u8); Channel::NUM_CHANNELS as usize],
+    proximity_touch_threshold: u8,
+    proximity_release_threshold: u8,
+    touch_debounce: DebounceNumber,
+    release_debounce: DebounceNumber,
+    charge_current: ChargeCurrent,
+    charge_time: ChargeTime,
+    first_filter_iterations: FirstFilterIterations,
+    second_filter_iterations: SecondFilterIterations,
+    sample_interval: ElectrodeSampleInterval,
+    electrode_charge: [(Option<ChargeCurrent>, Option<ChargeTime>); ELECTRODE_COUNT],
+    auto_config: AutoConfig,
+    electrode_config: ElectrodeConfig,
+}
+
+impl Default for Mpr121Config {
+    fn default() -> Self {
+        Mpr121Config {
+            electrode_filter: BaselineFilter {
+                rising: FilterRates {
+                    max_half_delta: 0x01,
+                    noise_half_delta: 0x01,
+                    noise_count_limit: 0x0e,
+                    filter_delay_count_limit: 0x00,
+                },
+                falling: FilterRates {
+                    max_half_delta: 0x01,
+                    noise_half_delta: 0x05,
+                    noise_count_limit: 0x01,
+                    filter_delay_count_limit: 0x00,
+                },
+                touched: TouchedFilterRates {
+                    noise_half_delta: 0x00,
+                    noise_count_limit: 0x00,
+                    filter_delay_count_limit: 0x00,
+                },
+            },
+            proximity_filter: BaselineFilter::default(),
+            thresholds: [(
+                Self::DEFAULT_TOUCH_THRESHOLD,
+                Self::DEFAULT_RELEASE_THRESHOLD,
+            ); Channel::NUM_CHANNELS as usize],
+            proximity_touch_threshold: 0,
+            proximity_release_threshold: 0,
+            touch_debounce: DebounceNumber::Zero,
+            release_debounce: DebounceNumber::Zero,
+            charge_current: ChargeCurrent::new(16).expect("16µA is a valid charge current"),
+            charge_time: ChargeTime::Micros0_5,
+            first_filter_iterations: FirstFilterIterations::Samples6,
+            second_filter_iterations: SecondFilterIterations::Samples4,
+            sample_interval: ElectrodeSampleInterval::Millis1,
+            electrode_charge: [(None, None); ELECTRODE_COUNT],
+            auto_config: AutoConfig::default(),
+            electrode_config: ElectrodeConfig::default(),
+        }
+    }
+}
+
+impl Mpr121Config {
+    /// Default Threshold
+    pub const DEFAULT_TOUCH_THRESHOLD: u8 = 12;
+    /// Default Release
+    pub const DEFAULT_RELEASE_THRESHOLD: u8 = 6;
+
+    /// Sets the baseline filter of the 12 touch electrodes. See 5.5 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    pub fn with_electrode_filter(mut self, filter: BaselineFilter) -> Self {
+        self.electrode_filter = filter;
+        self
+    }
+
+    /// Sets the baseline filter of the ELEPROX electrode. See 5.5 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    pub fn with_proximity_filter(mut self, filter: BaselineFilter) -> Self {
+        self.proximity_filter = filter;
+        self
+    }
+
+    /// Sets the touch and release threshold of all touch electrodes, see `Mpr121::set_thresholds`.
+    pub fn with_thresholds(mut self, touch: u8, release: u8) -> Self {
+        self.thresholds = [(touch, release); Channel::NUM_CHANNELS as usize];
+        self
+    }
+
+    /// Sets the touch and release threshold of a single touch electrode, see `Mpr121::set_channel_thresholds`.
+    pub fn with_channel_thresholds(mut self, channel: Channel, touch: u8, release: u8) -> Self {
+        self.thresholds[usize::from(u8::from(channel))] = (touch, release);
+        self
+    }
+
+    /// Sets the touch and release threshold of the ELEPROX electrode.
+    pub fn with_proximity_thresholds(mut self, touch: u8, release: u8) -> Self {
+        self.proximity_touch_threshold = touch;
+        self.proximity_release_threshold = release;
+        self
+    }
+
+    /// Sets the debounce count for both touch and release. See 5.7 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    pub fn with_debounce(mut self, touch: DebounceNumber, release: DebounceNumber) -> Self {
+        self.touch_debounce = touch;
+        self.release_debounce = release;
+        self
+    }
+
+    /// Sets the global charge current and charge time, which is used by every electrode that does not override it.
+    /// See 5.8 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    pub fn with_charge(mut self, current: ChargeCurrent, time: ChargeTime) -> Self {
+        self.charge_current = current;
+        self.charge_time = time;
+        self
+    }
+
+    /// Sets how many samples the first filter takes (FFI), and how many of its results the second filter averages (SFI).
+    /// More iterations reduce the noise, but lengthen the response time. The first filter iterations are shared with the
+    /// [auto-configuration](AutoConfig::first_filter_iterations). See 5.8 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    pub fn with_filter_iterations(
+        mut self,
+        first: FirstFilterIterations,
+        second: SecondFilterIterations,
+    ) -> Self {
+        self.first_filter_iterations = first;
+        self.auto_config.first_filter_iterations = first;
+        self.second_filter_iterations = second;
+        self
+    }
+
+    /// Sets the period between two measurements of the electrodes (ESI), which trades the response time against the power consumption.
+    /// See 5.8 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    pub fn with_sample_interval(mut self, interval: ElectrodeSampleInterval) -> Self {
+        self.sample_interval = interval;
+        self
+    }
+
+    /// Sets the charge current and charge time of a single electrode, `None` selects the global setting.
+    /// Note that a successful auto-configuration overrides these values.
+    pub fn with_electrode_charge(
+        mut self,
+        electrode: Electrode,
+        current: Option<ChargeCurrent>,
+        time: Option<ChargeTime>,
+    ) -> Self {
+        self.electrode_charge[electrode_index(electrode)] = (current, time);
+        self
+    }
+
+    /// Sets the auto configuration, which the controller uses to setup the charging parameters
+    /// whenever it is transitioned from STOP to START mode. Use [AutoConfig::disabled] to configure the charge manually.
+    /// The first filter iterations of the auto-configuration are replaced by the configured ones, see [with_filter_iterations](Self::with_filter_iterations).
+    pub fn with_auto_config(mut self, auto_config: AutoConfig) -> Self {
+        self.auto_config = AutoConfig {
+            first_filter_iterations: self.first_filter_iterations,
+            ..auto_config
+        };
+        self
+    }
+
+    /// Sets how many electrodes, starting from ELE0, are enabled for touch sensing.
+    /// Like on the device itself, values above 12 enable all 12 electrodes.
+    pub fn with_electrode_count(mut self, count: u8) -> Self {
+        self.electrode_config.electrode_count = count.min(Channel::NUM_CHANNELS);
+        self
+    }
+
+    /// Selects which electrodes are combined into the ELEPROX electrode for proximity detection.
+    pub fn with_proximity_mode(mut self, mode: ProximityMode) -> Self {
+        self.electrode_config.proximity_mode = mode;
+        self
+    }
+
+    /// Sets the calibration lock, which controls the baseline tracking. See 5.11 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    pub fn with_calibration_lock(mut self, calibration_lock: CalibrationLock) -> Self {
+        self.electrode_config.calibration_lock = calibration_lock;
+        self
+    }
+
+    /// Sets which electrodes are enabled when the device is started, see [ElectrodeConfig]
+    pub fn with_electrode_config(mut self, electrode_config: ElectrodeConfig) -> Self {
+        self.electrode_config = ElectrodeConfig {
+            electrode_count: electrode_config.electrode_count.min(Channel::NUM_CHANNELS),
+            ..electrode_config
+        };
+        self
+    }
+
+    /// Returns the baseline filter of the 12 touch electrodes
+    pub fn electrode_filter(&self) -> BaselineFilter {
+        self.electrode_filter
+    }
+
+    /// Returns the baseline filter of the ELEPROX electrode
+    pub fn proximity_filter(&self) -> BaselineFilter {
+        self.proximity_filter
+    }
+
+    /// Returns the (touch, release) thresholds of all touch electrodes, stored at the index of the channel
+    pub fn thresholds(&self) -> [(u8, u8); Channel::NUM_CHANNELS as usize] {
+        self.thresholds
+    }
+
+    /// Returns the (touch, release) thresholds of the channel
+    pub fn channel_thresholds(&self, channel: Channel) -> (u8, u8) {
+        self.thresholds[usize::from(u8::from(channel))]
+    }
+
+    /// Returns the (touch, release) thresholds of the ELEPROX electrode
+    pub fn proximity_thresholds(&self) -> (u8, u8) {
+        (
+            self.proximity_touch_threshold,
+            self.proximity_release_threshold,
+        )
+    }
+
+    /// Returns the (touch, release) debounce count
+    pub fn debounce(&self) -> (DebounceNumber, DebounceNumber) {
+        (self.touch_debounce, self.release_debounce)
+    }
+
+    /// Returns the global (current, time) charge settings
+    pub fn charge(&self) -> (ChargeCurrent, ChargeTime) {
+        (self.charge_current, self.charge_time)
+    }
+
+    /// Returns the (first, second) filter iterations
+    pub fn filter_iterations(&self) -> (FirstFilterIterations, SecondFilterIterations) {
+        (self.first_filter_iterations, self.second_filter_iterations)
+    }
+
+    /// Returns the period between two measurements of the electrodes
+    pub fn sample_interval(&self) -> ElectrodeSampleInterval {
+        self.sample_interval
+    }
+
+    /// Returns the (current, time) charge settings of the electrode, `None` if it uses the global setting
+    pub fn electrode_charge(
+        &self,
+        electrode: Electrode,
+    ) -> (Option<ChargeCurrent>, Option<ChargeTime>) {
+        self.electrode_charge[electrode_index(electrode)]
+    }
+
+    /// Returns the auto-configuration of the charge settings
+    pub fn auto_config(&self) -> AutoConfig {
+        self.auto_config
+    }
+
+    /// Returns how many electrodes, starting from ELE0, are enabled for touch sensing
+    pub fn electrode_count(&self) -> u8 {
+        self.electrode_config.electrode_count
+    }
+
+    /// Returns which electrodes are combined into the ELEPROX electrode
+    pub fn proximity_mode(&self) -> ProximityMode {
+        self.electrode_config.proximity_mode
+    }
+
+    /// Returns the calibration lock, which controls the baseline tracking
+    pub fn calibration_lock(&self) -> CalibrationLock {
+        self.electrode_config.calibration_lock
+    }
+
+    /// Returns which electrodes are enabled when the device is started
+    pub fn electrode_config(&self) -> ElectrodeConfig {
+        self.electrode_config
+    }
+
+    /// The first register read by [from_registers](Self::from_registers), the start of the baseline filter registers
+    pub(crate) const FIRST_REGISTER: Register = Register::MaximumHalfDeltaRising;
+    /// The number of registers decoded by [from_registers](Self::from_registers), up to the target level of the auto-configuration
+    pub(crate) const LENGTH: usize =
+        (Register::TargetLevel as usize) - (Self::FIRST_REGISTER as usize) + 1;
+
+    /// Returns the values of the AFE1 (FFI, CDC) and AFE2 (CDT, SFI, ESI) registers
+    pub(crate) fn afe_register_values(&self) -> [u8; 2] {
+        [
+            (u8::from(self.first_filter_iterations) << afe::FFI_SHIFT)
+                | self.charge_current.micro_amps(),
+            (u8::from(self.charge_time) << afe::CDT_SHIFT)
+                | (u8::from(self.second_filter_iterations) << afe::SFI_SHIFT)
+                | u8::from(self.sample_interval),
+        ]
+    }
+
+    /// Returns the values of the charge current registers (0x5F to 0x6B) and the charge time registers (0x6C to 0x72)
+    pub(crate) fn electrode_charge_register_values(&self) -> ([u8; ELECTRODE_COUNT], [u8; 7]) {
+        let mut currents = [0u8; ELECTRODE_COUNT];
+        let mut times = [0u8; 7];
+        for (i, (current, time)) in self.electrode_charge.into_iter().enumerate() {
+            currents[i] = current.map_or(0, ChargeCurrent::micro_amps);
+            times[i / 2] |= time.map_or(0, u8::from) << ((i % 2) * 4);
+        }
+        (currents, times)
+    }
+
+    /// Decodes the configuration from the registers 0x2B to 0x7F. Returns the register with an invalid value,
+    /// which is only possible for the global charge current and charge time.
+    ///
+    /// The first filter iterations of AFE1 and AutoConfig0 are decoded separately, so a mismatch shows up in the result.
+    pub(crate) fn from_registers(registers: &[u8; Self::LENGTH]) -> Result<Self, Register> {
+        const CHARGE_TIME_MASK: u8 = 0b111;
+        let index = |reg: Register| usize::from(u8::from(reg) - u8::from(Self::FIRST_REGISTER));
+        let read = |reg: Register| registers[index(reg)];
+
+        let mut thresholds = [(0u8, 0u8); Channel::NUM_CHANNELS as usize];
+        for channel in Channel::iter() {
+            thresholds[usize::from(u8::from(channel))] = (
+                read(Register::get_threshold_register(channel)),
+                read(Register::get_release_register(channel)),
+            );
+        }
+
+        let mut electrode_charge = [(None, None); ELECTRODE_COUNT];
+        for electrode in Electrode::iter() {
+            let current =
+                read(Register::get_charge_current(electrode)) & ChargeCurrent::MAX_MICRO_AMPS;
+            let time = (read(Register::get_charge_time(electrode))
+                >> Register::get_charge_time_shift(electrode))
+                & CHARGE_TIME_MASK;
+            // A value of zero selects the global setting
+            electrode_charge[electrode_index(electrode)] =
+                (ChargeCurrent::new(current), ChargeTime::try_from(time).ok());
+        }
+
+        let debounce = read(Register::Debounce);
+        let afe_1 = read(Register::GlobalChargeDischargeCurrentConfig);
+        let afe_2 = read(Register::GlobalChargeDischargeTimeConfig);
+        let auto_config_values = [
+            Register::AutoConfig0,
+            Register::AutoConfig1,
+            Register::UpSideLimit,
+            Register::LowSideLimit,
+            Register::TargetLevel,
+        ]
+        .map(read);
+
+        Ok(Mpr121Config {
+            electrode_filter: BaselineFilter::from_register_values(
+                ELECTRODE_FILTER_REGISTERS.map(read),
+            ),
+            proximity_filter: BaselineFilter::from_register_values(
+                PROXIMITY_FILTER_REGISTERS.map(read),
+            ),
+            thresholds,
+            proximity_touch_threshold: read(Register::EleproxTouchThreshold),
+            proximity_release_threshold: read(Register::EleproxReleaseThreshold),
+            touch_debounce: DebounceNumber::try_from(debounce & 0b111)
+                .expect("Every three bit value is a valid debounce number"),
+            release_debounce: DebounceNumber::try_from((debounce >> 4) & 0b111)
+                .expect("Every three bit value is a valid debounce number"),
+            charge_current: ChargeCurrent::new(afe_1 & afe::CDC_MASK)
+                .ok_or(Register::GlobalChargeDischargeCurrentConfig)?,
+            charge_time: ChargeTime::try_from((afe_2 & afe::CDT_MASK) >> afe::CDT_SHIFT)
+                .map_err(|_| Register::GlobalChargeDischargeTimeConfig)?,
+            first_filter_iterations: FirstFilterIterations::try_from(
+                (afe_1 & afe::FFI_MASK) >> afe::FFI_SHIFT,
+            )
+            .expect("Every two bit value is a valid first filter iterations"),
+            second_filter_iterations: SecondFilterIterations::try_from(
+                (afe_2 & afe::SFI_MASK) >> afe::SFI_SHIFT,
+            )
+            .expect("Every two bit value is a valid second filter iterations"),
+            sample_interval: ElectrodeSampleInterval::try_from(afe_2 & afe::ESI_MASK)
+                .expect("Every three bit value is a valid sample interval"),
+            electrode_charge,
+            auto_config: AutoConfig::from_register_values(auto_config_values),
+            electrode_config: ElectrodeConfig::from_register(read(Register::Ecr)),
+        })
+    }
+}
+
+/// Returns the index of the electrode in the per-electrode settings, ELEPROX follows the touch electrodes
+fn electrode_index(electrode: Electrode) -> usize {
+    match electrode {
+        Electrode::Channel(channel) => usize::from(u8::from(channel)),
+        Electrode::Eleprox => usize::from(Channel::NUM_CHANNELS),
+    }
+}
+
+/// The content of the Electrode Configuration Register (ECR), which selects the electrodes that are measured in RUN mode.
+/// Enabling any electrode puts the device into RUN mode, see section 5.11 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ElectrodeConfig {
+    /// The baseline tracking and initial baseline value
+    pub calibration_lock: CalibrationLock,
+    /// The electrodes combined into the ELEPROX electrode
+    pub proximity_mode: ProximityMode,
+    /// How many electrodes, starting from ELE0, are enabled for touch sensing. Values above 12 enable all 12 electrodes.
+    pub electrode_count: u8,
+}
+
+impl Default for ElectrodeConfig {
+    fn default() -> Self {
+        ElectrodeConfig {
+            calibration_lock: CalibrationLock::TrackingInit5Bit,
+            proximity_mode: ProximityMode::Disabled,
+            electrode_count: Channel::NUM_CHANNELS,
+        }
+    }
+}
+
+impl ElectrodeConfig {
+    /// Returns the ECR value for this configuration
+    pub(crate) fn to_register(self) -> u8 {
+        (u8::from(self.calibration_lock) << ecr::CL_SHIFT)
+            | (u8::from(self.proximity_mode) << ecr::ELEPROX_EN_SHIFT)
+            | self.electrode_count.min(ecr::ELE_EN_MASK)
+    }
+
+    /// Decodes the configuration from the ECR value
+    pub(crate) fn from_register(value: u8) -> Self {
+        ElectrodeConfig {
+            calibration_lock: CalibrationLock::try_from((value & ecr::CL_MASK) >> ecr::CL_SHIFT)
+                .expect("Every two bit value is a valid calibration lock"),
+            proximity_mode: ProximityMode::try_from(
+                (value & ecr::ELEPROX_EN_MASK) >> ecr::ELEPROX_EN_SHIFT,
+            )
+            .expect("Every two bit value is a valid proximity mode"),
+            electrode_count: (value & ecr::ELE_EN_MASK).min(Channel::NUM_CHANNELS),
+        }
+    }
+}
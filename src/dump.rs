@@ -0,0 +1,235 @@
+//! A raw copy of the register map for bring-up and support, read with `Mpr121::dump_registers`.
+//!
+//! The [Display](core::fmt::Display) implementation of [RegisterDump] lists the registers with their address and raw value,
+//! decoded into the fields of the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf), e.g.
+//! `0x5e ECR 0x8c: CL=2 ELEPROX_EN=0 ELE_EN=12`. The fields are printed as raw numbers, so invalid values are visible as well.
+use core::fmt;
+
+use crate::{registers::*, Channel, ChannelSet, Electrode};
+
+/// The content of the registers 0x00 (touch status) to 0x7F (auto-configuration target level).
+/// The write-only soft reset register (0x80) and the GPIO PWM registers are not part of the dump.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterDump {
+    registers: [u8; RegisterDump::LENGTH],
+}
+
+impl RegisterDump {
+    /// The number of registers in a dump
+    pub const LENGTH: usize = (Register::TargetLevel as usize) + 1;
+
+    pub(crate) fn new(registers: [u8; Self::LENGTH]) -> Self {
+        RegisterDump { registers }
+    }
+
+    /// Returns the value of the register at the given address, or `None` if the address is beyond the target level register
+    pub fn get(&self, address: u8) -> Option<u8> {
+        self.registers.get(usize::from(address)).copied()
+    }
+
+    /// Returns the values of all registers, stored at their address
+    pub fn as_bytes(&self) -> &[u8; Self::LENGTH] {
+        &self.registers
+    }
+
+    fn read(&self, reg: Register) -> u8 {
+        self.registers[usize::from(u8::from(reg))]
+    }
+
+    fn read_u16(&self, reg: Register) -> u16 {
+        let index = usize::from(u8::from(reg));
+        u16::from_le_bytes([self.registers[index], self.registers[index + 1]])
+    }
+
+    /// Writes the address, the name and the raw value of the register, followed by the field separator
+    fn write_register(&self, f: &mut fmt::Formatter<'_>, reg: Register, name: &str) -> fmt::Result {
+        write!(f, "{:#04x} {name} {:#04x}:", u8::from(reg), self.read(reg))
+    }
+
+    fn write_filter(&self, f: &mut fmt::Formatter<'_>, first: Register, name: &str) -> fmt::Result {
+        let index = usize::from(u8::from(first));
+        let values = &self.registers[index..index + 11];
+        for (offset, direction) in [(0, "RISING"), (4, "FALLING")] {
+            writeln!(
+                f,
+                "{:#04x} {name} {direction}: MHD={} NHD={} NCL={} FDL={}",
+                index + offset,
+                values[offset],
+                values[offset + 1],
+                values[offset + 2],
+                values[offset + 3]
+            )?;
+        }
+        writeln!(
+            f,
+            "{:#04x} {name} TOUCHED: NHD={} NCL={} FDL={}",
+            index + 8,
+            values[8],
+            values[9],
+            values[10]
+        )
+    }
+}
+
+fn bit(value: u8, bit: u8) -> u8 {
+    (value >> bit) & 0b1
+}
+
+/// Formats an electrode with its datasheet name, e.g. `ELE3` or `EPROX`
+struct Name(Electrode);
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Electrode::Channel(channel) => write!(f, "ELE{}", u8::from(channel)),
+            Electrode::Eleprox => write!(f, "EPROX"),
+        }
+    }
+}
+
+impl fmt::Display for RegisterDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let touch_status = self.read_u16(Register::TouchStatus0_7);
+        writeln!(
+            f,
+            "0x00 TOUCH STATUS {touch_status:#06x}: ELE={:?} EPROX={} OVCF={}",
+            ChannelSet::from_bits(touch_status),
+            bit(self.read(Register::TouchStatus8_11), 4),
+            bit(self.read(Register::TouchStatus8_11), 7)
+        )?;
+        let oor_status = self.read_u16(Register::OORStatus);
+        writeln!(
+            f,
+            "0x02 OOR STATUS {oor_status:#06x}: ELE={:?} EPROX={} ARFF={} ACFF={}",
+            ChannelSet::from_bits(oor_status),
+            bit(self.read(Register::EleproxOORStatus), 4),
+            bit(self.read(Register::EleproxOORStatus), 6),
+            bit(self.read(Register::EleproxOORStatus), 7)
+        )?;
+
+        for electrode in Electrode::iter() {
+            let (filtered, baseline) = match electrode {
+                Electrode::Channel(channel) => (
+                    Register::get_filtered_data_msb(channel),
+                    Register::get_baseline(channel),
+                ),
+                Electrode::Eleprox => (Register::EleproxFiltDataMSB, Register::EleproxBaseLine),
+            };
+            writeln!(
+                f,
+                "{:#04x} {} FILTERED {}, {:#04x} BASELINE {:#04x} (<< 2 = {})",
+                u8::from(filtered),
+                Name(electrode),
+                self.read_u16(filtered) & FILTERED_DATA_MASK,
+                u8::from(baseline),
+                self.read(baseline),
+                u16::from(self.read(baseline)) << 2
+            )?;
+        }
+
+        self.write_filter(f, Register::MaximumHalfDeltaRising, "ELE FILTER")?;
+        self.write_filter(f, Register::ProxMaximumHalfDeltaRising, "EPROX FILTER")?;
+
+        for channel in Channel::iter() {
+            let touch = Register::get_threshold_register(channel);
+            writeln!(
+                f,
+                "{:#04x} {} THRESHOLDS: TTH={} RTH={}",
+                u8::from(touch),
+                Name(channel.into()),
+                self.read(touch),
+                self.read(Register::get_release_register(channel))
+            )?;
+        }
+        writeln!(
+            f,
+            "0x59 EPROX THRESHOLDS: TTH={} RTH={}",
+            self.read(Register::EleproxTouchThreshold),
+            self.read(Register::EleproxReleaseThreshold)
+        )?;
+
+        let debounce = self.read(Register::Debounce);
+        self.write_register(f, Register::Debounce, "DEBOUNCE")?;
+        writeln!(f, " DR={} DT={}", (debounce >> 4) & 0b111, debounce & 0b111)?;
+        let afe_1 = self.read(Register::GlobalChargeDischargeCurrentConfig);
+        self.write_register(f, Register::GlobalChargeDischargeCurrentConfig, "AFE1")?;
+        writeln!(
+            f,
+            " FFI={} CDC={}uA",
+            (afe_1 & afe::FFI_MASK) >> afe::FFI_SHIFT,
+            afe_1 & afe::CDC_MASK
+        )?;
+        let afe_2 = self.read(Register::GlobalChargeDischargeTimeConfig);
+        self.write_register(f, Register::GlobalChargeDischargeTimeConfig, "AFE2")?;
+        writeln!(
+            f,
+            " CDT={} SFI={} ESI={}",
+            (afe_2 & afe::CDT_MASK) >> afe::CDT_SHIFT,
+            (afe_2 & afe::SFI_MASK) >> afe::SFI_SHIFT,
+            afe_2 & afe::ESI_MASK
+        )?;
+        let ecr_value = self.read(Register::Ecr);
+        self.write_register(f, Register::Ecr, "ECR")?;
+        writeln!(
+            f,
+            " CL={} ELEPROX_EN={} ELE_EN={}",
+            (ecr_value & ecr::CL_MASK) >> ecr::CL_SHIFT,
+            (ecr_value & ecr::ELEPROX_EN_MASK) >> ecr::ELEPROX_EN_SHIFT,
+            ecr_value & ecr::ELE_EN_MASK
+        )?;
+
+        for electrode in Electrode::iter() {
+            let current = Register::get_charge_current(electrode);
+            let time = Register::get_charge_time(electrode);
+            writeln!(
+                f,
+                "{:#04x} {} CDC={}uA, {:#04x} CDT={}",
+                u8::from(current),
+                Name(electrode),
+                self.read(current) & 0b11_1111,
+                u8::from(time),
+                (self.read(time) >> Register::get_charge_time_shift(electrode)) & 0b111
+            )?;
+        }
+
+        writeln!(
+            f,
+            "0x73 GPIO: CTL0={:#010b} CTL1={:#010b} DAT={:#010b} DIR={:#010b} EN={:#010b}",
+            self.read(Register::GpioControl0),
+            self.read(Register::GpioControl1),
+            self.read(Register::GpioData),
+            self.read(Register::GpioDirection),
+            self.read(Register::GpioEnable)
+        )?;
+
+        let control_0 = self.read(Register::AutoConfig0);
+        self.write_register(f, Register::AutoConfig0, "AUTO-CONFIG 0")?;
+        writeln!(
+            f,
+            " FFI={} RETRY={} BVA={} ARE={} ACE={}",
+            control_0 >> 6,
+            (control_0 >> 4) & 0b11,
+            (control_0 >> 2) & 0b11,
+            bit(control_0, 1),
+            bit(control_0, 0)
+        )?;
+        let control_1 = self.read(Register::AutoConfig1);
+        self.write_register(f, Register::AutoConfig1, "AUTO-CONFIG 1")?;
+        writeln!(
+            f,
+            " SCTS={} OORIE={} ARFIE={} ACFIE={}",
+            bit(control_1, 7),
+            bit(control_1, 2),
+            bit(control_1, 1),
+            bit(control_1, 0)
+        )?;
+        writeln!(
+            f,
+            "0x7d AUTO-CONFIG LIMITS: USL={} LSL={} TL={}",
+            self.read(Register::UpSideLimit),
+            self.read(Register::LowSideLimit),
+            self.read(Register::TargetLevel)
+        )
+    }
+}
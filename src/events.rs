@@ -0,0 +1,88 @@
+//! Touch and release events, which are derived from the changes of the touch status between two polls.
+//!
+//! The driver remembers the touch status of its last poll, so [TouchEvents] only contains the transitions since then.
+//! Besides the touch electrodes the ELEPROX electrode and the over-current flag are reported as events as well.
+use crate::Channel;
+
+/// A change of the touch status since the last poll
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TouchEvent {
+    /// The electrode of the channel is touched
+    Touched(Channel),
+    /// The electrode of the channel is released
+    Released(Channel),
+    /// The ELEPROX electrode detects an object in proximity
+    ProximityDetected,
+    /// The object in proximity of the ELEPROX electrode is gone
+    ProximityLost,
+    /// The over-current flag was set, which indicates a short of the REXT pin
+    OverCurrentDetected,
+    /// The over-current flag was cleared
+    OverCurrentCleared,
+}
+
+/// An iterator over the events between two touch status readings, ordered by channel,
+/// followed by the ELEPROX and over-current events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TouchEvents {
+    current: u16,
+    changed: u16,
+}
+
+impl TouchEvents {
+    /// The touch status bits of the 12 touch electrodes
+    const ELECTRODES_MASK: u16 = 0x0fff;
+    /// The touch status bit of the ELEPROX electrode
+    const ELEPROX_BIT: u32 = 12;
+    /// The over-current flag of the touch status registers
+    const OVER_CURRENT_BIT: u32 = 15;
+    /// All bits of the touch status registers which are reported as events
+    pub(crate) const STATUS_MASK: u16 =
+        Self::ELECTRODES_MASK | (1 << Self::ELEPROX_BIT) | (1 << Self::OVER_CURRENT_BIT);
+
+    /// Creates the events between the previous and the current value of the touch status registers
+    pub(crate) fn new(previous: u16, current: u16) -> Self {
+        TouchEvents {
+            current,
+            changed: (previous ^ current) & Self::STATUS_MASK,
+        }
+    }
+}
+
+impl Iterator for TouchEvents {
+    type Item = TouchEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.changed == 0 {
+            return None;
+        }
+        let bit = self.changed.trailing_zeros();
+        self.changed &= !(1 << bit);
+        let is_set = self.current & (1 << bit) > 0;
+        let event = match (bit, is_set) {
+            (Self::ELEPROX_BIT, true) => TouchEvent::ProximityDetected,
+            (Self::ELEPROX_BIT, false) => TouchEvent::ProximityLost,
+            (Self::OVER_CURRENT_BIT, true) => TouchEvent::OverCurrentDetected,
+            (Self::OVER_CURRENT_BIT, false) => TouchEvent::OverCurrentCleared,
+            (channel, touched) => {
+                let channel = Channel::try_from(channel as u8)
+                    .expect("Only the bits of the touch electrodes are left");
+                if touched {
+                    TouchEvent::Touched(channel)
+                } else {
+                    TouchEvent::Released(channel)
+                }
+            }
+        };
+        Some(event)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.changed.count_ones() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for TouchEvents {}
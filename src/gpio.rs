@@ -0,0 +1,192 @@
+//! Electrodes 4 to 11 that are not used for touch sensing can be used as GPIO or LED driver instead.
+//!
+//! See section 5.13 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf) and [note AN3894](https://www.nxp.com/docs/en/application-note/AN3894.pdf).
+//! An electrode can only be configured as GPIO if it is not enabled for touch sensing by the ECR, i.e. the electrode count has to be
+//! lower than the pin number and proximity detection must not combine all 12 electrodes.
+//!
+//! Pins configured as output additionally support a 4 bit PWM, which can be used to dim LEDs.
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use crate::Channel;
+
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// This enum represents the electrodes that can be used as GPIO or LED driver
+pub enum GpioPin {
+    Four = 4,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Eleven,
+}
+
+impl GpioPin {
+    /// Returns the bit mask of the pin within the GPIO registers
+    pub(crate) fn get_bit_mask(self) -> u8 {
+        1 << (u8::from(self) - u8::from(GpioPin::Four))
+    }
+
+    /// Returns the touch channel this pin shares its electrode with
+    pub fn channel(self) -> Channel {
+        Channel::try_from(u8::from(self)).expect("Every GPIO pin is a valid channel")
+    }
+}
+
+impl TryFrom<Channel> for GpioPin {
+    type Error = Channel;
+
+    /// Converts the channel into its GPIO pin, returns the channel if it can not be used as GPIO
+    fn try_from(channel: Channel) -> Result<Self, Self::Error> {
+        GpioPin::try_from(u8::from(channel)).map_err(|_| channel)
+    }
+}
+
+/// The function of a GPIO pin, see table 15 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum GpioMode {
+    /// The GPIO function is disabled, the pin is high impedance
+    Disabled,
+    /// Input without internal pull resistor
+    Input,
+    /// Input with internal pull-up resistor
+    InputPullUp,
+    /// Input with internal pull-down resistor
+    InputPullDown,
+    /// CMOS push-pull output
+    Output,
+    /// Open drain output which can only drive high (PMOS), used as LED driver
+    OutputHighSide,
+    /// Open drain output which can only drive low (NMOS), used as LED driver
+    OutputLowSide,
+}
+
+impl GpioMode {
+    /// Returns the (EN, DIR, CTL0, CTL1) bits of the mode
+    pub(crate) fn bits(self) -> (bool, bool, bool, bool) {
+        match self {
+            GpioMode::Disabled => (false, false, false, false),
+            GpioMode::Input => (true, false, false, false),
+            GpioMode::InputPullDown => (true, false, true, false),
+            GpioMode::InputPullUp => (true, false, true, true),
+            GpioMode::Output => (true, true, false, false),
+            GpioMode::OutputLowSide => (true, true, true, false),
+            GpioMode::OutputHighSide => (true, true, true, true),
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+pub use blocking_pin::Mpr121Pin;
+
+#[cfg(feature = "sync")]
+mod blocking_pin {
+    use core::cell::RefCell;
+
+    use embedded_hal::digital::{
+        self, ErrorKind, ErrorType, InputPin, OutputPin, StatefulOutputPin,
+    };
+    use embedded_hal::i2c::{self, I2c};
+    use embedded_hal::pwm::{self, SetDutyCycle};
+
+    use super::{GpioMode, GpioPin};
+    use crate::{mpr121::blocking::Mpr121, Mpr121Error};
+
+    impl<E: i2c::Error> digital::Error for Mpr121Error<E> {
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::Other
+        }
+    }
+
+    impl<E: i2c::Error> pwm::Error for Mpr121Error<E> {
+        fn kind(&self) -> pwm::ErrorKind {
+            pwm::ErrorKind::Other
+        }
+    }
+
+    /// A GPIO pin of the sensor which implements the [embedded_hal::digital] traits,
+    /// and [SetDutyCycle] for dimming LEDs when configured as output.
+    ///
+    /// As every pin needs access to the I2C bus, the sensor is shared through a [RefCell], which allows several
+    /// pins to be used alongside each other and alongside the touch functionality of the sensor.
+    pub struct Mpr121Pin<'a, I2C: I2c> {
+        driver: &'a RefCell<Mpr121<I2C>>,
+        pin: GpioPin,
+    }
+
+    impl<'a, I2C: I2c> Mpr121Pin<'a, I2C> {
+        /// Configures the pin in the given mode, see [configure_gpio](Mpr121::configure_gpio).
+        ///
+        /// In the event of an error [Mpr121Error] is returned
+        pub fn new(
+            driver: &'a RefCell<Mpr121<I2C>>,
+            pin: GpioPin,
+            mode: GpioMode,
+        ) -> Result<Self, Mpr121Error<I2C::Error>> {
+            driver.borrow_mut().configure_gpio(pin, mode)?;
+            Ok(Mpr121Pin { driver, pin })
+        }
+
+        /// Returns the GPIO pin this driver controls
+        pub fn pin(&self) -> GpioPin {
+            self.pin
+        }
+    }
+
+    impl<I2C: I2c> ErrorType for Mpr121Pin<'_, I2C> {
+        type Error = Mpr121Error<I2C::Error>;
+    }
+
+    impl<I2C: I2c> OutputPin for Mpr121Pin<'_, I2C> {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.driver.borrow_mut().set_gpio_low(self.pin)
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.driver.borrow_mut().set_gpio_high(self.pin)
+        }
+    }
+
+    impl<I2C: I2c> StatefulOutputPin for Mpr121Pin<'_, I2C> {
+        fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+            self.driver.borrow_mut().is_gpio_high(self.pin)
+        }
+
+        fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.is_set_high()?)
+        }
+
+        fn toggle(&mut self) -> Result<(), Self::Error> {
+            self.driver.borrow_mut().toggle_gpio(self.pin)
+        }
+    }
+
+    impl<I2C: I2c> InputPin for Mpr121Pin<'_, I2C> {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            self.driver.borrow_mut().is_gpio_high(self.pin)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.is_high()?)
+        }
+    }
+
+    impl<I2C: I2c> pwm::ErrorType for Mpr121Pin<'_, I2C> {
+        type Error = Mpr121Error<I2C::Error>;
+    }
+
+    impl<I2C: I2c> SetDutyCycle for Mpr121Pin<'_, I2C> {
+        fn max_duty_cycle(&self) -> u16 {
+            Mpr121::<I2C>::MAX_PWM_DUTY.into()
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Mpr121Error<I2C::Error>> {
+            let duty = duty.min(self.max_duty_cycle()) as u8;
+            self.driver.borrow_mut().set_gpio_pwm(self.pin, duty)
+        }
+    }
+}
@@ -8,37 +8,48 @@
 //! since this is probably the most widely used one.
 //!
 //! When working with this crate you can either use it in Synchronous/Blocking mode with the [embedded-hal](https://crates.io/crates/embedded-hal) or in Asynchronous mode with the [embedded-hal-async](https://crates.io/crates/embedded-hal-async).
-//!This can be done by using the features `sync` and `async`. This crate does not pull in the `std` library and thus is fully `no-std`.
+//!This can be done by using the features `sync` and `async`, which provide `mpr121::blocking::Mpr121` and `mpr121::asynch::Mpr121`.
+//! Both features can be enabled at the same time. This crate does not pull in the `std` library and thus is fully `no-std`.
 //! For MCU scale devices [Embassy](https://github.com/embassy-rs/embassy) is a valid framework to use the async feature or [Tokio](https://tokio.rs/) when using Linux/MacOS based devices.
 #![no_std]
 #![deny(unsafe_code, warnings)]
 
+use gpio::GpioPin;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use registers::Register;
 
-mod communications;
+pub mod config;
+pub mod dump;
+pub mod events;
+pub mod gpio;
 pub mod mpr121;
 mod registers;
-
-#[cfg(all(feature = "sync", feature = "async"))]
-compile_error!("You cannot use both sync and async features at the same time. Please choose one.");
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod snapshot;
 
 #[cfg(all(not(feature = "async"), not(feature = "sync")))]
 compile_error!("You must enable either the sync or async feature. Please choose one.");
 
-/// The MPR121 Device has an Enumeration of potential driver errors, which are held in the enum below
+/// The MPR121 Device has an Enumeration of potential driver errors, which are held in the enum below.
+/// `E` is the error type of the I2C bus, which is passed on for failed bus operations.
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
-pub enum Mpr121Error {
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Mpr121Error<E> {
     ///If an operation exceeds the channel count (typically 12).
     ChannelExceed,
-    ///If a read operation failed, contains the address that failed.
-    ReadError(Register),
+    ///If a read operation failed, contains the address that failed and the error of the bus.
+    ReadError(Register, E),
     /// If a data conversion failed, contains the address that failed to convert from
     DataConversionError(Register),
-    ///If a write operation failed, contains the address that failed.
-    WriteError(Register),
-    ///If sending the reset signal failed, contains the register that failed.
-    ResetFailed { was_read: bool, reg: Register },
+    ///If a write operation failed, contains the address that failed and the error of the bus.
+    WriteError(Register, E),
+    ///If sending the reset signal failed, contains the register that failed and the error of the bus.
+    ResetFailed {
+        was_read: bool,
+        reg: Register,
+        source: E,
+    },
     /// During Startup if there is an overcurrent detection, the driver will fail to initialise indicating a hardware fault
     OverCurrent,
     /// Wrong Device Connected, this can also happen if the device state is invalid possible due to a short circuit/overcurrent event
@@ -47,14 +58,85 @@ pub enum Mpr121Error {
         expected: u8,
         actual: u8,
     },
+    /// The electrode of the GPIO pin is currently enabled for touch or proximity sensing and can not be used as GPIO
+    PinInUse(GpioPin),
+    /// Waiting for the IRQ pin failed, contains the kind of the error of the pin
+    IrqPinError(
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))] embedded_hal::digital::ErrorKind,
+    ),
+    /// The electrode of the channel is not enabled by the electrode count, so the device does not measure it
+    ChannelDisabled(Channel),
+    /// The electrode configuration enables neither touch nor proximity electrodes, which would leave the device in STOP mode
+    NothingEnabled,
+}
+
+impl<E> Mpr121Error<E> {
+    /// Returns the error of the I2C bus if the error was caused by a failed bus operation.
+    /// Use `embedded_hal::i2c::Error::kind` to tell e.g. a missing device (NACK) from a lost arbitration.
+    pub fn bus_error(&self) -> Option<&E> {
+        match self {
+            Mpr121Error::ReadError(_, source)
+            | Mpr121Error::WriteError(_, source)
+            | Mpr121Error::ResetFailed { source, .. } => Some(source),
+            _ => None,
+        }
+    }
 }
 
+impl<E: core::fmt::Debug> core::fmt::Display for Mpr121Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Mpr121Error::ChannelExceed => write!(f, "channel exceeds the number of channels"),
+            Mpr121Error::ReadError(reg, source) => {
+                write!(f, "failed to read register {reg:?}: {source:?}")
+            }
+            Mpr121Error::DataConversionError(reg) => {
+                write!(f, "invalid value in register {reg:?}")
+            }
+            Mpr121Error::WriteError(reg, source) => {
+                write!(f, "failed to write register {reg:?}: {source:?}")
+            }
+            Mpr121Error::ResetFailed {
+                was_read,
+                reg,
+                source,
+            } => write!(
+                f,
+                "soft reset failed to {} register {reg:?}: {source:?}",
+                if *was_read { "read" } else { "write" }
+            ),
+            Mpr121Error::OverCurrent => write!(f, "over-current detected on the REXT pin"),
+            Mpr121Error::WrongDevice {
+                mismatched_register,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "unexpected device, register {mismatched_register:?} is {actual:#04x} instead of {expected:#04x}"
+            ),
+            Mpr121Error::PinInUse(pin) => {
+                write!(f, "{pin:?} is in use as touch or proximity electrode")
+            }
+            Mpr121Error::IrqPinError(kind) => write!(f, "failed to wait for the IRQ pin: {kind:?}"),
+            Mpr121Error::ChannelDisabled(channel) => {
+                write!(f, "{channel:?} is not enabled for touch sensing")
+            }
+            Mpr121Error::NothingEnabled => {
+                write!(f, "neither touch nor proximity electrodes are enabled")
+            }
+        }
+    }
+}
+
+impl<E: core::fmt::Debug> core::error::Error for Mpr121Error<E> {}
+
 ///The four values the sensor can be addressed as. Note that the address of the device is determined by
 /// where the `ADDR` pin is connected to. Default is used if no connection, or a connection to `VSS` is made.
 ///
 /// Have a look at page 4 "serial communication" for further specification.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Mpr121Address {
     Default = 0x5a,
     Vdd = 0x5b,
@@ -64,6 +146,7 @@ pub enum Mpr121Address {
 
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// This enum represents the channels of the sensor and is used to get the corresponding touch values
 pub enum Channel {
     Zero,
@@ -82,14 +165,528 @@ pub enum Channel {
 
 impl Channel {
     pub const NUM_CHANNELS: u8 = 12;
+    /// All channels in ascending order
+    pub const ALL: [Channel; Channel::NUM_CHANNELS as usize] = [
+        Channel::Zero,
+        Channel::One,
+        Channel::Two,
+        Channel::Three,
+        Channel::Four,
+        Channel::Five,
+        Channel::Six,
+        Channel::Seven,
+        Channel::Eight,
+        Channel::Nine,
+        Channel::Ten,
+        Channel::Eleven,
+    ];
+
+    /// Returns an iterator over all channels in ascending order
+    pub fn iter() -> impl Iterator<Item = Channel> {
+        Self::ALL.into_iter()
+    }
+
     /// Returns the bit mask associated with the selected channel
     pub(crate) fn get_bit_mask(self) -> u16 {
         1 << u8::from(self)
     }
 }
 
+/// A set of channels, e.g. the touched channels, which is stored as a bit mask in the layout of the status registers.
+///
+/// ```
+/// use mpr121_hal::{Channel, ChannelSet};
+///
+/// let mut touched = ChannelSet::from_bits(0b1000_0000_1000);
+/// assert!(touched.contains(Channel::Three));
+/// touched.insert(Channel::Zero);
+/// touched.remove(Channel::Eleven);
+/// assert_eq!(touched.iter().collect::<Vec<_>>(), [Channel::Zero, Channel::Three]);
+/// assert_eq!(format!("{touched:?}"), "{Zero, Three}");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelSet(u16);
+
+impl ChannelSet {
+    /// The set without any channel
+    pub const EMPTY: ChannelSet = ChannelSet(0);
+    /// The set of all 12 channels
+    pub const ALL: ChannelSet = ChannelSet(0x0fff);
+
+    /// Creates the set from a bit mask where each bit 0..12 represents the channel of the same number, higher bits are ignored
+    pub const fn from_bits(bits: u16) -> Self {
+        ChannelSet(bits & Self::ALL.0)
+    }
+
+    /// Returns the bit mask where each bit 0..12 represents the channel of the same number
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the set of the first `count` channels, starting from channel zero
+    pub fn first(count: u8) -> Self {
+        Self::from_bits((1 << count.min(Channel::NUM_CHANNELS)) - 1)
+    }
+
+    /// Returns true if the channel is part of the set
+    pub fn contains(self, channel: Channel) -> bool {
+        self.0 & channel.get_bit_mask() > 0
+    }
+
+    /// Adds the channel to the set
+    pub fn insert(&mut self, channel: Channel) {
+        self.0 |= channel.get_bit_mask();
+    }
+
+    /// Removes the channel from the set
+    pub fn remove(&mut self, channel: Channel) {
+        self.0 &= !channel.get_bit_mask();
+    }
+
+    /// Returns true if the set contains no channel
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the number of channels in the set
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns the channels which are in either set
+    pub fn union(self, other: ChannelSet) -> Self {
+        ChannelSet(self.0 | other.0)
+    }
+
+    /// Returns the channels which are in both sets
+    pub fn intersection(self, other: ChannelSet) -> Self {
+        ChannelSet(self.0 & other.0)
+    }
+
+    /// Returns the channels which are in this set, but not in the other
+    pub fn difference(self, other: ChannelSet) -> Self {
+        ChannelSet(self.0 & !other.0)
+    }
+
+    /// Returns the channels which are in exactly one of the sets
+    pub fn symmetric_difference(self, other: ChannelSet) -> Self {
+        ChannelSet(self.0 ^ other.0)
+    }
+
+    /// Returns the channels which are not in the set
+    pub fn complement(self) -> Self {
+        ChannelSet(!self.0 & Self::ALL.0)
+    }
+
+    /// Returns an iterator over the channels of the set in ascending order
+    pub fn iter(self) -> ChannelSetIter {
+        ChannelSetIter(self.0)
+    }
+}
+
+impl core::fmt::Debug for ChannelSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl From<Channel> for ChannelSet {
+    fn from(channel: Channel) -> Self {
+        ChannelSet(channel.get_bit_mask())
+    }
+}
+
+impl FromIterator<Channel> for ChannelSet {
+    fn from_iter<T: IntoIterator<Item = Channel>>(iter: T) -> Self {
+        let mut set = ChannelSet::EMPTY;
+        for channel in iter {
+            set.insert(channel);
+        }
+        set
+    }
+}
+
+impl IntoIterator for ChannelSet {
+    type Item = Channel;
+    type IntoIter = ChannelSetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl core::ops::BitOr for ChannelSet {
+    type Output = ChannelSet;
+
+    fn bitor(self, rhs: ChannelSet) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl core::ops::BitAnd for ChannelSet {
+    type Output = ChannelSet;
+
+    fn bitand(self, rhs: ChannelSet) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl core::ops::BitXor for ChannelSet {
+    type Output = ChannelSet;
+
+    fn bitxor(self, rhs: ChannelSet) -> Self::Output {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl core::ops::Sub for ChannelSet {
+    type Output = ChannelSet;
+
+    fn sub(self, rhs: ChannelSet) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl core::ops::Not for ChannelSet {
+    type Output = ChannelSet;
+
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+/// An iterator over the channels of a [ChannelSet] in ascending order
+#[derive(Clone, Debug)]
+pub struct ChannelSetIter(u16);
+
+impl Iterator for ChannelSetIter {
+    type Item = Channel;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros() as u8;
+        self.0 &= !(1 << bit);
+        Some(Channel::try_from(bit).expect("A channel set only contains valid channels"))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.0.count_ones() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ChannelSetIter {}
+
+/// A value for each of the 12 channels, e.g. the filtered data of all channels, which is indexed by [Channel].
+///
+/// ```
+/// use mpr121_hal::{Channel, ChannelValues};
+///
+/// let values = ChannelValues::from([0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 110]);
+/// assert_eq!(values[Channel::Three], 30);
+/// assert_eq!(values.iter().last(), Some((Channel::Eleven, &110)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelValues<T>([T; Channel::NUM_CHANNELS as usize]);
+
+impl<T> ChannelValues<T> {
+    /// Returns an iterator over the channels in ascending order and their values
+    pub fn iter(&self) -> impl Iterator<Item = (Channel, &T)> {
+        Channel::iter().zip(self.0.iter())
+    }
+
+    /// Returns the values, stored at the index of their channel
+    pub fn as_array(&self) -> &[T; Channel::NUM_CHANNELS as usize] {
+        &self.0
+    }
+
+    /// Returns the values, stored at the index of their channel
+    pub fn into_array(self) -> [T; Channel::NUM_CHANNELS as usize] {
+        self.0
+    }
+}
+
+impl<T> From<[T; Channel::NUM_CHANNELS as usize]> for ChannelValues<T> {
+    fn from(values: [T; Channel::NUM_CHANNELS as usize]) -> Self {
+        ChannelValues(values)
+    }
+}
+
+impl<T> core::ops::Index<Channel> for ChannelValues<T> {
+    type Output = T;
+
+    fn index(&self, channel: Channel) -> &Self::Output {
+        &self.0[usize::from(u8::from(channel))]
+    }
+}
+
+/// An electrode of the sensor. Next to the 12 touch [Channel]s the MPR121 has a 13th virtual electrode (ELEPROX),
+/// which is formed by combining several of the touch electrodes and is used for proximity detection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Electrode {
+    Channel(Channel),
+    Eleprox,
+}
+
+impl Electrode {
+    /// Returns an iterator over the 12 touch electrodes in ascending order, followed by ELEPROX
+    pub fn iter() -> impl Iterator<Item = Electrode> {
+        Channel::iter()
+            .map(Electrode::Channel)
+            .chain([Electrode::Eleprox])
+    }
+}
+
+impl From<Channel> for Electrode {
+    fn from(channel: Channel) -> Self {
+        Electrode::Channel(channel)
+    }
+}
+
+/// The charge/discharge current of an electrode in µA. Valid values range from 1µA to 63µA,
+/// see section 5.8 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChargeCurrent(u8);
+
+impl ChargeCurrent {
+    /// The largest supported charge current in µA
+    pub const MAX_MICRO_AMPS: u8 = 0b0011_1111;
+
+    /// Creates the charge current from a value in µA. Returns `None` if the value is 0 or exceeds [Self::MAX_MICRO_AMPS].
+    pub const fn new(micro_amps: u8) -> Option<Self> {
+        if micro_amps == 0 || micro_amps > Self::MAX_MICRO_AMPS {
+            None
+        } else {
+            Some(ChargeCurrent(micro_amps))
+        }
+    }
+
+    /// Returns the charge current in µA
+    pub const fn micro_amps(self) -> u8 {
+        self.0
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// This enum represents the charge/discharge time of an electrode, see section 5.8 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
+pub enum ChargeTime {
+    Micros0_5 = 1,
+    Micros1,
+    Micros2,
+    Micros4,
+    Micros8,
+    Micros16,
+    Micros32,
+}
+
+#[repr(u8)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive,
+)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// This enum represents which electrodes are combined into the ELEPROX proximity electrode, see section 5.11 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
+pub enum ProximityMode {
+    /// Proximity detection is disabled
+    #[default]
+    Disabled,
+    /// ELE0 and ELE1 are combined for proximity detection
+    Electrodes0To1,
+    /// ELE0 to ELE3 are combined for proximity detection
+    Electrodes0To3,
+    /// All 12 electrodes are combined for proximity detection
+    Electrodes0To11,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// This enum represents the number of samples taken by the first filter (FFI), see section 5.8 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
+pub enum FirstFilterIterations {
+    Samples6,
+    Samples10,
+    Samples18,
+    Samples34,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// This enum represents the number of first filter results averaged by the second filter (SFI), see section 5.8 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
+pub enum SecondFilterIterations {
+    Samples4,
+    Samples6,
+    Samples10,
+    Samples18,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// This enum represents the period between two measurements of the electrodes (ESI), see section 5.8 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+/// Together with the [SecondFilterIterations] it determines the response time, longer intervals lower the power consumption.
+pub enum ElectrodeSampleInterval {
+    Millis1,
+    Millis2,
+    Millis4,
+    Millis8,
+    Millis16,
+    Millis32,
+    Millis64,
+    Millis128,
+}
+
+/// The result of the last auto-configuration and auto-reconfiguration, see section 5.12 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AutoConfigStatus {
+    /// Auto-configuration failed (ACFF), the charge settings of at least one electrode are out of range
+    pub auto_config_failed: bool,
+    /// Auto-reconfiguration failed (ARFF)
+    pub auto_reconfig_failed: bool,
+}
+
+/// The out-of-range status of all electrodes, see section 5.12 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+/// An electrode is out of range if the auto-configuration could not find charge settings within the configured limits,
+/// which usually means that the electrode is shorted or not connected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutOfRangeStatus {
+    /// The channels whose electrodes are out of range
+    pub electrodes: ChannelSet,
+    /// The ELEPROX electrode is out of range
+    pub eleprox: bool,
+    /// The failure flags of the auto-configuration
+    pub auto_config: AutoConfigStatus,
+}
+
+impl OutOfRangeStatus {
+    /// Decodes the status from the two out-of-range status registers
+    pub(crate) fn from_registers(bits: u16) -> Self {
+        const ELEPROX_MASK: u16 = 0b1 << 12;
+        const AUTO_RECONFIG_FAIL_FLAG_MASK: u16 = 0b1 << 14;
+        const AUTO_CONFIG_FAIL_FLAG_MASK: u16 = 0b1 << 15;
+        OutOfRangeStatus {
+            electrodes: ChannelSet::from_bits(bits),
+            eleprox: (bits & ELEPROX_MASK) > 0,
+            auto_config: AutoConfigStatus {
+                auto_config_failed: (bits & AUTO_CONFIG_FAIL_FLAG_MASK) > 0,
+                auto_reconfig_failed: (bits & AUTO_RECONFIG_FAIL_FLAG_MASK) > 0,
+            },
+        }
+    }
+
+    /// Returns true if the electrode of the channel is out of range
+    pub fn is_out_of_range(&self, channel: Channel) -> bool {
+        self.electrodes.contains(channel)
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+/// This enum represents the calibration lock (CL) bits of the ECR, which control the baseline tracking.
+/// See section 5.11 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
+pub enum CalibrationLock {
+    /// Baseline tracking enabled, the initial baseline is the current baseline value
+    TrackingFromCurrent,
+    /// Baseline tracking disabled
+    TrackingDisabled,
+    /// Baseline tracking enabled, the initial baseline is loaded with the 5 MSB of the first electrode data
+    TrackingInit5Bit,
+    /// Baseline tracking enabled, the initial baseline is loaded with all 10 bits of the first electrode data
+    TrackingInit10Bit,
+}
+
+/// Filter settings that are used while the data is rising or falling relative to the baseline.
+/// See section 5.5 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf) and [note AN3891](https://www.nxp.com/docs/en/application-note/AN3891.pdf).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FilterRates {
+    /// Maximum Half Delta (MHD), valid values are 1 to 63
+    pub max_half_delta: u8,
+    /// Noise Half Delta (NHD), valid values are 1 to 63
+    pub noise_half_delta: u8,
+    /// Noise Count Limit (NCL), valid values are 0 to 255
+    pub noise_count_limit: u8,
+    /// Filter Delay Count Limit (FDL), valid values are 0 to 255
+    pub filter_delay_count_limit: u8,
+}
+
+/// Filter settings that are used while the electrode is touched. The MPR121 has no Maximum Half Delta for this case.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TouchedFilterRates {
+    /// Noise Half Delta (NHD), valid values are 1 to 63
+    pub noise_half_delta: u8,
+    /// Noise Count Limit (NCL), valid values are 0 to 255
+    pub noise_count_limit: u8,
+    /// Filter Delay Count Limit (FDL), valid values are 0 to 255
+    pub filter_delay_count_limit: u8,
+}
+
+/// The baseline filter of either the touch electrodes or the ELEPROX electrode.
+/// The `Default` value mirrors the values of the device after a reset.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BaselineFilter {
+    pub rising: FilterRates,
+    pub falling: FilterRates,
+    pub touched: TouchedFilterRates,
+}
+
+impl BaselineFilter {
+    /// Returns the values in register order (MHD, NHD, NCL, FDL rising, then falling, then NHD, NCL, FDL touched)
+    pub(crate) fn register_values(&self) -> [u8; 11] {
+        [
+            self.rising.max_half_delta,
+            self.rising.noise_half_delta,
+            self.rising.noise_count_limit,
+            self.rising.filter_delay_count_limit,
+            self.falling.max_half_delta,
+            self.falling.noise_half_delta,
+            self.falling.noise_count_limit,
+            self.falling.filter_delay_count_limit,
+            self.touched.noise_half_delta,
+            self.touched.noise_count_limit,
+            self.touched.filter_delay_count_limit,
+        ]
+    }
+
+    /// Decodes the filter from the values in the order of [register_values](Self::register_values)
+    pub(crate) fn from_register_values(values: [u8; 11]) -> Self {
+        BaselineFilter {
+            rising: FilterRates {
+                max_half_delta: values[0],
+                noise_half_delta: values[1],
+                noise_count_limit: values[2],
+                filter_delay_count_limit: values[3],
+            },
+            falling: FilterRates {
+                max_half_delta: values[4],
+                noise_half_delta: values[5],
+                noise_count_limit: values[6],
+                filter_delay_count_limit: values[7],
+            },
+            touched: TouchedFilterRates {
+                noise_half_delta: values[8],
+                noise_count_limit: values[9],
+                filter_delay_count_limit: values[10],
+            },
+        }
+    }
+}
+
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 /// This enum represents the number of debounces see section 5.7 in the [MPR121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
 pub enum DebounceNumber {
     Zero,
@@ -0,0 +1,36 @@
+//! The async driver, which uses the [embedded_hal_async] traits.
+use embedded_hal::digital::Error as _;
+use embedded_hal_async::{
+    delay::DelayNs,
+    digital::Wait,
+    i2c::{I2c, Operation},
+};
+// Keeps the functions of the shared driver implementation async
+use maybe_async::must_be_async as maybe_async;
+
+// The shared driver implementation is included by both variants on purpose
+#[allow(clippy::duplicate_mod)]
+#[path = "driver/mod.rs"]
+mod driver;
+
+pub use driver::Mpr121;
+
+use crate::{events::TouchEvents, Mpr121Error};
+
+impl<I2C: I2c, MODE> Mpr121<I2C, MODE> {
+    /// Waits until the device signals a change of the touch status on its active low IRQ pin, then reads the touch status,
+    /// which releases the IRQ pin again, and returns the [events](crate::events::TouchEvent) since the last poll, see [poll_events](Self::poll_events).
+    ///
+    /// The pin is awaited to be low instead of a falling edge, so a change which happened before this call returns immediately.
+    /// The events can be empty if the change was reverted before the status was read, or if the IRQ was caused by an out-of-range interrupt.
+    /// In the event of an error [Mpr121Error] is returned
+    pub async fn wait_for_change(
+        &mut self,
+        irq: &mut impl Wait,
+    ) -> Result<TouchEvents, Mpr121Error<I2C::Error>> {
+        irq.wait_for_low()
+            .await
+            .map_err(|e| Mpr121Error::IrqPinError(e.kind()))?;
+        self.poll_events().await
+    }
+}
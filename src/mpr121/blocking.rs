@@ -0,0 +1,14 @@
+//! The blocking driver, which uses the [embedded_hal] traits.
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{I2c, Operation},
+};
+// Turns the async functions of the shared driver implementation into blocking functions
+use maybe_async::must_be_sync as maybe_async;
+
+// The shared driver implementation is included by both variants on purpose
+#[allow(clippy::duplicate_mod)]
+#[path = "driver/mod.rs"]
+mod driver;
+
+pub use driver::Mpr121;
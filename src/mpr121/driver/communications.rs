@@ -0,0 +1,104 @@
+use super::{maybe_async, I2c, Mpr121, Operation};
+use crate::{registers::*, Mpr121Error};
+
+impl<I2C: I2c, MODE> Mpr121<I2C, MODE> {
+    #[maybe_async]
+    //Write implementation. Returns an error if a write operation failed. The error contains the failing register.
+    //Registers that require STOP mode are only written by methods of a stopped driver, see the type state of [Mpr121].
+    pub(crate) async fn write_register(
+        &mut self,
+        reg: Register,
+        value: u8,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        debug_assert!(
+            !(reg.require_stop() && self.is_running()),
+            "{reg:?} can only be written in STOP mode"
+        );
+        let addr_val: u8 = self.addr.into();
+
+        self.i2c
+            .write(addr_val, &[reg.into(), value])
+            .await
+            .map_err(|e| Mpr121Error::WriteError(reg, e))?;
+
+        match reg {
+            Register::Ecr => self.ecr = value,
+            Register::SoftReset => self.ecr = Register::Ecr.get_initial_value(),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    #[maybe_async]
+    //Reads the value, returns Err, if reading failed.
+    pub(crate) async fn read_reg8(&mut self, reg: Register) -> Result<u8, Mpr121Error<I2C::Error>> {
+        let mut val = [0u8];
+        self.i2c
+            .write_read(self.addr.into(), &[reg.into()], &mut val)
+            .await
+            .map_err(|e| Mpr121Error::ReadError(reg, e))?;
+        Ok(val[0])
+    }
+
+    #[maybe_async]
+    //Reads the value, returns Err, if reading failed.
+    pub(crate) async fn read_reg16(
+        &mut self,
+        reg: Register,
+    ) -> Result<u16, Mpr121Error<I2C::Error>> {
+        let mut val = [0u8, 0u8];
+        self.i2c
+            .write_read(self.addr.into(), &[reg.into()], &mut val)
+            .await
+            .map_err(|e| Mpr121Error::ReadError(reg, e))?;
+        Ok(u16::from_le_bytes(val))
+    }
+
+    #[maybe_async]
+    //Reads consecutive registers starting at reg in a single transaction, relying on the address auto increment of the device.
+    pub(crate) async fn read_registers(
+        &mut self,
+        reg: Register,
+        buffer: &mut [u8],
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.i2c
+            .write_read(self.addr.into(), &[reg.into()], buffer)
+            .await
+            .map_err(|e| Mpr121Error::ReadError(reg, e))
+    }
+
+    #[maybe_async]
+    //Writes consecutive registers starting at reg in a single transaction, relying on the address auto increment of the device.
+    //The registers must not include the ECR or the soft reset register, which are cached by the driver.
+    pub(crate) async fn write_registers(
+        &mut self,
+        reg: Register,
+        values: &[u8],
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        debug_assert!(
+            !(reg.require_stop() && self.is_running()),
+            "{reg:?} can only be written in STOP mode"
+        );
+        self.i2c
+            .transaction(
+                self.addr.into(),
+                &mut [Operation::Write(&[reg.into()]), Operation::Write(values)],
+            )
+            .await
+            .map_err(|e| Mpr121Error::WriteError(reg, e))
+    }
+
+    #[maybe_async]
+    //Replaces the bits of the mask with the bits of value in a read-modify-write, for registers holding several fields.
+    pub(crate) async fn update_register(
+        &mut self,
+        reg: Register,
+        mask: u8,
+        value: u8,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let bits = self.read_reg8(reg).await?;
+        self.write_register(reg, (bits & !mask) | (value & mask))
+            .await
+    }
+}
@@ -0,0 +1,21 @@
+use super::{maybe_async, I2c, Mpr121};
+use crate::{config::Mpr121Config, dump::RegisterDump, registers::*, Mpr121Error};
+
+impl<I2C: I2c, MODE> Mpr121<I2C, MODE> {
+    /// Reads all registers from the touch status (0x00) up to the auto-configuration target level (0x7F) into a [RegisterDump],
+    /// in one transaction for the electrode data and one for the configuration, as the register pointer of the device
+    /// wraps around at the end of each block. Print the dump to decode the registers.
+    ///
+    /// Like [get_touched](Self::get_touched), this reads the touch status and therefore clears the IRQ.
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn dump_registers(&mut self) -> Result<RegisterDump, Mpr121Error<I2C::Error>> {
+        let mut buffer = [0u8; RegisterDump::LENGTH];
+        let (data, config) =
+            buffer.split_at_mut(usize::from(u8::from(Mpr121Config::FIRST_REGISTER)));
+        self.read_registers(Register::TouchStatus0_7, data).await?;
+        self.read_registers(Mpr121Config::FIRST_REGISTER, config)
+            .await?;
+        Ok(RegisterDump::new(buffer))
+    }
+}
@@ -0,0 +1,17 @@
+use super::{maybe_async, I2c, Mpr121};
+use crate::{events::TouchEvents, registers::*, Mpr121Error};
+
+impl<I2C: I2c, MODE> Mpr121<I2C, MODE> {
+    /// Reads the touch status and returns the [events](crate::events::TouchEvent) since the last call, i.e. which channels were touched or released,
+    /// and whether the proximity detection or the over-current flag changed. The first call compares against an untouched sensor.
+    ///
+    /// Changes which are reverted between two calls are not reported, so poll at least as often as the touch status can change.
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn poll_events(&mut self) -> Result<TouchEvents, Mpr121Error<I2C::Error>> {
+        let current = self.read_reg16(Register::TouchStatus0_7).await? & TouchEvents::STATUS_MASK;
+        let events = TouchEvents::new(self.touch_status, current);
+        self.touch_status = current;
+        Ok(events)
+    }
+}
@@ -0,0 +1,135 @@
+use super::{maybe_async, I2c, Mpr121};
+use crate::{
+    gpio::{GpioMode, GpioPin},
+    registers::*,
+    Mpr121Error, ProximityMode,
+};
+
+impl<I2C: I2c, MODE> Mpr121<I2C, MODE> {
+    /// The largest PWM duty cycle, at which the pin is permanently on
+    pub const MAX_PWM_DUTY: u8 = 0b1111;
+
+    /// Configures the function of a GPIO pin.
+    ///
+    /// Returns [Mpr121Error::PinInUse] if the electrode is currently enabled for touch or proximity sensing,
+    /// otherwise [Mpr121Error] in the event of a communication error
+    #[maybe_async]
+    pub async fn configure_gpio(
+        &mut self,
+        pin: GpioPin,
+        mode: GpioMode,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        if mode != GpioMode::Disabled && self.is_electrode_in_use(pin) {
+            return Err(Mpr121Error::PinInUse(pin));
+        }
+
+        let (enable, direction, control_0, control_1) = mode.bits();
+        // Disable the pin first, so that it does not glitch through an intermediate configuration
+        self.update_gpio_bit(Register::GpioEnable, pin, false)
+            .await?;
+        self.update_gpio_bit(Register::GpioControl0, pin, control_0)
+            .await?;
+        self.update_gpio_bit(Register::GpioControl1, pin, control_1)
+            .await?;
+        self.update_gpio_bit(Register::GpioDirection, pin, direction)
+            .await?;
+        self.update_gpio_bit(Register::GpioEnable, pin, enable)
+            .await?;
+        Ok(())
+    }
+
+    /// Drives an output pin high. For the LED driver modes this turns the LED on.
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_gpio_high(&mut self, pin: GpioPin) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.write_register(Register::GpioDataSet, pin.get_bit_mask())
+            .await
+    }
+
+    /// Drives an output pin low. For the LED driver modes this turns the LED off.
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_gpio_low(&mut self, pin: GpioPin) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.write_register(Register::GpioDataClear, pin.get_bit_mask())
+            .await
+    }
+
+    /// Toggles the level of an output pin.
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn toggle_gpio(&mut self, pin: GpioPin) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.write_register(Register::GpioDataToggle, pin.get_bit_mask())
+            .await
+    }
+
+    /// Returns true if the pin is high. For inputs this is the level on the pin, for outputs the level the pin is driven to.
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn is_gpio_high(&mut self, pin: GpioPin) -> Result<bool, Mpr121Error<I2C::Error>> {
+        let read = self.read_reg8(Register::GpioData).await?;
+        Ok(read & pin.get_bit_mask() > 0)
+    }
+
+    /// Sets the PWM duty cycle of an output pin, which is used to dim LEDs driven by the pin.
+    /// The duty cycle ranges from 0 (off) to [MAX_PWM_DUTY](Self::MAX_PWM_DUTY), larger values are clamped.
+    ///
+    /// The PWM is only active while the pin is driven high, so this also sets the pin high for any duty other than 0.
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_gpio_pwm(
+        &mut self,
+        pin: GpioPin,
+        duty: u8,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let duty = duty.min(Self::MAX_PWM_DUTY);
+        let shift = Register::get_pwm_shift(pin);
+        self.update_register(
+            Register::get_pwm(pin),
+            Self::MAX_PWM_DUTY << shift,
+            duty << shift,
+        )
+        .await?;
+
+        if duty == 0 {
+            self.set_gpio_low(pin).await
+        } else {
+            self.set_gpio_high(pin).await
+        }
+    }
+
+    /// Reads the PWM duty cycle of a pin, see [set_gpio_pwm](Self::set_gpio_pwm).
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_gpio_pwm(&mut self, pin: GpioPin) -> Result<u8, Mpr121Error<I2C::Error>> {
+        let read = self.read_reg8(Register::get_pwm(pin)).await?;
+        Ok((read >> Register::get_pwm_shift(pin)) & Self::MAX_PWM_DUTY)
+    }
+
+    /// Returns true if the ECR currently enables the electrode for touch or proximity sensing
+    fn is_electrode_in_use(&self, pin: GpioPin) -> bool {
+        self.enabled_electrodes().contains(pin.channel())
+            || self.get_proximity_mode() == ProximityMode::Electrodes0To11
+    }
+
+    /// Sets or clears the bit of the pin in the given GPIO register
+    #[maybe_async]
+    async fn update_gpio_bit(
+        &mut self,
+        reg: Register,
+        pin: GpioPin,
+        set: bool,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let mut bits = self.read_reg8(reg).await?;
+        if set {
+            bits |= pin.get_bit_mask();
+        } else {
+            bits &= !pin.get_bit_mask();
+        }
+        self.write_register(reg, bits).await
+    }
+}
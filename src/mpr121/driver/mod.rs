@@ -0,0 +1,942 @@
+//! The driver implementation shared by the [blocking](crate::mpr121::blocking) and [asynch](crate::mpr121::asynch) modules.
+//! Each of them includes this module and provides the `maybe_async` attribute, which turns the functions below into blocking
+//! or async functions, alongside the matching `I2c` and `DelayNs` traits.
+use core::marker::PhantomData;
+
+use super::{maybe_async, DelayNs, I2c, Operation};
+use crate::mpr121::{Running, Stopped};
+use crate::{
+    config::{AutoConfig, ElectrodeConfig, Mpr121Config},
+    AutoConfigStatus, Mpr121Address, Mpr121Error, OutOfRangeStatus,
+};
+use crate::{
+    registers::*, BaselineFilter, CalibrationLock, Channel, ChannelSet, ChannelValues,
+    ChargeCurrent, ChargeTime, DebounceNumber, Electrode, ElectrodeSampleInterval,
+    FirstFilterIterations, ProximityMode, SecondFilterIterations,
+};
+
+mod communications;
+mod dump;
+mod events;
+mod gpio;
+mod snapshot;
+
+/// This is the sensor itself and takes in an I2C Device or bus.
+/// See the examples folder for more details.
+/// The driver is available in a blocking and an async variant, see the [mpr121](crate::mpr121) module.
+///
+/// Most configuration registers of the device can only be written in STOP mode, while touch detection only works in RUN mode.
+/// The driver therefore tracks the mode in its type: [new](Self::new) returns a `Mpr121<I2C, Running>`, the configuration methods
+/// only exist on `Mpr121<I2C, Stopped>`, and [stop](Self::stop) and [start](Mpr121::start) move between the two.
+///
+/// The driver keeps a copy of the Electrode Configuration Register (ECR), so that it does not have to read it back before every write.
+/// If another component writes to the device, call [resync](Self::resync) to update the copy. A stopped driver also keeps the ECR
+/// it was running with, so [resume](Mpr121::resume) can restart the same electrodes.
+pub struct Mpr121<I2C: I2c, MODE = Running> {
+    pub(crate) i2c: I2C,
+    pub(crate) addr: Mpr121Address,
+    /// The last value written to, or read from the ECR
+    pub(crate) ecr: u8,
+    /// The ECR of the last RUN mode, which is restored by [resume](Mpr121::resume)
+    pub(crate) run_ecr: u8,
+    /// The touch status of the last [poll_events](Self::poll_events)
+    pub(crate) touch_status: u16,
+    _mode: PhantomData<MODE>,
+}
+
+impl<I2C: I2c> Mpr121<I2C, Running> {
+    ///Creates the driver for the given I²C ports. Assumes that the I²C port is configured as master.
+    ///
+    /// The device is initialised with the [default configuration](Mpr121Config::default), which uses
+    /// the same values as the Adafruit implementation, except for threshold values.
+    /// Use [with_config](Self::with_config) to change those.
+    ///
+    /// In the event of an error, returns [Mpr121Error]
+    #[maybe_async]
+    pub async fn new(
+        i2c: I2C,
+        addr: Mpr121Address,
+        delay: &mut impl DelayNs,
+    ) -> Result<Self, Mpr121Error<I2C::Error>> {
+        Self::with_config(i2c, addr, delay, Mpr121Config::default()).await
+    }
+
+    ///Creates the driver for the given I²C ports and initialises the device with the given configuration.
+    /// Assumes that the I²C port is configured as master.
+    ///
+    /// In the event of an error, returns [Mpr121Error]
+    #[maybe_async]
+    pub async fn with_config(
+        i2c: I2C,
+        addr: Mpr121Address,
+        delay: &mut impl DelayNs,
+        config: Mpr121Config,
+    ) -> Result<Self, Mpr121Error<I2C::Error>> {
+        let mut dev = Mpr121::<I2C, Stopped> {
+            i2c,
+            addr,
+            ecr: Register::Ecr.get_initial_value(),
+            run_ecr: Register::Ecr.get_initial_value(),
+            touch_status: 0,
+            _mode: PhantomData,
+        };
+        dev.reset_verify(delay).await?;
+
+        // Check for overcurrent
+        if dev.is_over_current_set().await? {
+            return Err(Mpr121Error::OverCurrent);
+        }
+
+        dev.initialise_registers(&config).await?;
+        //enable electrodes and return to start mode // See Datasheet 5.11
+        dev.start(config.electrode_config()).await
+    }
+
+    /// Initializes the driver assuming the sensors address is the default one (0x5a).
+    /// If this fails, consider searching for the driver.
+    /// Or following the documentation on setting a driver address, and use [new](Self::new) to specify the address.
+    ///
+    /// Have a look at [new](Self::new) for further documentation.
+    #[maybe_async]
+    pub async fn new_default(
+        i2c: I2C,
+        delay: &mut impl DelayNs,
+    ) -> Result<Self, Mpr121Error<I2C::Error>> {
+        let result = Self::new(i2c, Mpr121Address::Default, delay).await?;
+        Ok(result)
+    }
+
+    /// Puts the device into STOP mode, in which it can be configured. The calibration lock is kept, and the electrode configuration
+    /// is remembered, so that [resume](Mpr121::resume) restarts the device with the same electrodes afterwards.
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn stop(mut self) -> Result<Mpr121<I2C, Stopped>, Mpr121Error<I2C::Error>> {
+        self.run_ecr = self.ecr;
+        self.write_register(Register::Ecr, self.ecr & ecr::CL_MASK)
+            .await?;
+        Ok(self.into_mode())
+    }
+
+    /// Selects which electrodes are combined into the ELEPROX electrode for proximity detection, or disables it.
+    /// The touch electrode configuration is left untouched. See 5.11 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    ///
+    /// Configure the [filter](Mpr121::set_proximity_filter) and [thresholds](Mpr121::set_proximity_thresholds) before starting the device.
+    /// If no touch electrode is enabled, disabling the proximity detection would stop the device, so [Mpr121Error::NothingEnabled]
+    /// is returned instead, use [stop](Self::stop) to do so.
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_proximity_mode(
+        &mut self,
+        mode: ProximityMode,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let value = (self.ecr & !ecr::ELEPROX_EN_MASK) | (u8::from(mode) << ecr::ELEPROX_EN_SHIFT);
+        self.write_running_ecr(value).await
+    }
+
+    /// Writes an ECR value that keeps the device in RUN mode, returns [Mpr121Error::NothingEnabled] for any other value
+    #[maybe_async]
+    async fn write_running_ecr(&mut self, value: u8) -> Result<(), Mpr121Error<I2C::Error>> {
+        if value & ecr::RUN_MODE_MASK == 0 {
+            return Err(Mpr121Error::NothingEnabled);
+        }
+        self.write_register(Register::Ecr, value).await
+    }
+
+    /// Sets the calibration lock, which controls the baseline tracking of the enabled electrodes. Use [CalibrationLock::TrackingDisabled]
+    /// to freeze the baselines, e.g. while a hand rests on the sensor for a long time, so the baseline does not drift into the touch,
+    /// and [CalibrationLock::TrackingFromCurrent] to resume the tracking from the frozen values.
+    /// The initial baseline of the other modes is only loaded on the next transition from STOP to RUN mode.
+    /// See 5.11 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_calibration_lock(
+        &mut self,
+        calibration_lock: CalibrationLock,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let value = (self.ecr & !ecr::CL_MASK) | (u8::from(calibration_lock) << ecr::CL_SHIFT);
+        self.write_register(Register::Ecr, value).await?;
+        Ok(())
+    }
+
+    /// Sets how many electrodes, starting from ELE0, are enabled for touch sensing. Like on the device itself, values above 12
+    /// enable all 12 electrodes. Scanning fewer electrodes saves power and shortens the response time, the spare electrodes
+    /// from ELE4 upwards can be used as [GPIO](crate::gpio). Enabling an electrode that is configured as GPIO disables its GPIO function.
+    ///
+    /// A count of 0 is only accepted while the proximity detection is enabled, otherwise the device would leave RUN mode and
+    /// [Mpr121Error::NothingEnabled] is returned, use [stop](Self::stop) instead.
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_electrode_count(&mut self, count: u8) -> Result<(), Mpr121Error<I2C::Error>> {
+        let value = (self.ecr & !ecr::ELE_EN_MASK) | count.min(Channel::NUM_CHANNELS);
+        self.write_running_ecr(value).await
+    }
+}
+
+impl<I2C: I2c> Mpr121<I2C, Stopped> {
+    /// Performs a software reset on the device, resetting the MPR121 Touch sensor back to default configuration
+    #[maybe_async]
+    pub async fn reset(&mut self) -> Result<(), Mpr121Error<I2C::Error>> {
+        let result = self
+            .write_register(Register::SoftReset, Self::SOFT_RESET_VALUE)
+            .await;
+
+        // Map any read/write errors to a failed reset error
+        result.map_err(|err| match err {
+                Mpr121Error::ReadError(reg, source) => Mpr121Error::ResetFailed {
+                    was_read: true,
+                    reg,
+                    source,
+                },
+                Mpr121Error::WriteError(reg, source) => Mpr121Error::ResetFailed {
+                    was_read: false,
+                    reg,
+                    source,
+                },
+                _ => {
+                    unreachable!("There should only be a read or write error at this stage, perhaps a lower level API has changed?")
+                }
+            })
+    }
+
+    /// Enables the electrodes of the given configuration, which puts the device into RUN mode. If auto-configuration is enabled, it runs
+    /// during this transition, use [get_auto_config_status](Mpr121::get_auto_config_status) to check whether it succeeded.
+    ///
+    /// A configuration that enables neither touch nor proximity electrodes would leave the device in STOP mode,
+    /// so [Mpr121Error::NothingEnabled] is returned for it.
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn start(
+        mut self,
+        electrode_config: ElectrodeConfig,
+    ) -> Result<Mpr121<I2C, Running>, Mpr121Error<I2C::Error>> {
+        let value = electrode_config.to_register();
+        if value & ecr::RUN_MODE_MASK == 0 {
+            return Err(Mpr121Error::NothingEnabled);
+        }
+        self.write_register(Register::Ecr, value).await?;
+        Ok(self.into_mode())
+    }
+
+    /// Restarts the device with the electrode configuration it was running with before [stop](Mpr121::stop).
+    ///
+    /// If the driver was never running, [Mpr121Error::NothingEnabled] is returned.
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn resume(self) -> Result<Mpr121<I2C, Running>, Mpr121Error<I2C::Error>> {
+        let electrode_config = self.previous_electrode_config();
+        self.start(electrode_config).await
+    }
+
+    /// Returns the electrode configuration the device was running with before [stop](Mpr121::stop), see [resume](Self::resume)
+    pub fn previous_electrode_config(&self) -> ElectrodeConfig {
+        ElectrodeConfig::from_register(self.run_ecr)
+    }
+
+    /// Set the touch and release threshold for all channels. Usually the touch threshold is a little bigger than the release
+    /// threshold. This creates some debounce characteristics. The correct thresholds depend on the application.
+    ///
+    /// Have a look at [note AN3892](https://www.nxp.com/docs/en/application-note/AN3892.pdf) of the mpr121 guidelines.
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_thresholds(
+        &mut self,
+        touch: u8,
+        release: u8,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.set_all_channel_thresholds([(touch, release); Channel::NUM_CHANNELS as usize])
+            .await
+    }
+
+    /// Set the touch and release threshold of a single channel, e.g. to use a lower threshold for small electrodes.
+    /// See [set_thresholds](Self::set_thresholds).
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_channel_thresholds(
+        &mut self,
+        channel: Channel,
+        touch: u8,
+        release: u8,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.write_registers(Register::get_threshold_register(channel), &[touch, release])
+            .await
+    }
+
+    /// Set the (touch, release) thresholds of all channels, stored at the index of the channel, in a single transaction.
+    /// See [set_thresholds](Self::set_thresholds).
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_all_channel_thresholds(
+        &mut self,
+        thresholds: [(u8, u8); Channel::NUM_CHANNELS as usize],
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let mut values = [0u8; 2 * Channel::NUM_CHANNELS as usize];
+        for (value, (touch, release)) in values.chunks_exact_mut(2).zip(thresholds) {
+            value[0] = touch;
+            value[1] = release;
+        }
+        self.write_registers(Register::get_threshold_register(Channel::Zero), &values)
+            .await
+    }
+
+    /// Sets the count for both touch and release. See 5.7 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_debounce(
+        &mut self,
+        trigger_debounce: DebounceNumber,
+        release_debounce: DebounceNumber,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let bits = (u8::from(release_debounce) << 4) | (u8::from(trigger_debounce));
+        self.write_register(Register::Debounce, bits).await?;
+        Ok(())
+    }
+
+    /// Configures the baseline filter of the 12 touch electrodes. See 5.5 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
+    /// and [note AN3891](https://www.nxp.com/docs/en/application-note/AN3891.pdf).
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_electrode_filter(
+        &mut self,
+        filter: &BaselineFilter,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        for (register, value) in ELECTRODE_FILTER_REGISTERS
+            .into_iter()
+            .zip(filter.register_values())
+        {
+            self.write_register(register, value).await?;
+        }
+        Ok(())
+    }
+
+    /// Set the touch and release threshold of the ELEPROX electrode. See [set_thresholds](Self::set_thresholds) for the touch electrodes.
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_proximity_thresholds(
+        &mut self,
+        touch: u8,
+        release: u8,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.write_register(Register::EleproxTouchThreshold, touch)
+            .await?;
+        self.write_register(Register::EleproxReleaseThreshold, release)
+            .await?;
+        Ok(())
+    }
+
+    /// Configures the baseline filter of the ELEPROX electrode. See 5.5 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf)
+    /// and [note AN3893](https://www.nxp.com/docs/en/application-note/AN3893.pdf) for values suited for proximity detection.
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_proximity_filter(
+        &mut self,
+        filter: &BaselineFilter,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        for (register, value) in PROXIMITY_FILTER_REGISTERS
+            .into_iter()
+            .zip(filter.register_values())
+        {
+            self.write_register(register, value).await?;
+        }
+        Ok(())
+    }
+
+    /// Configures the auto-configuration of the charge current and charge time. The configuration only runs on the next
+    /// transition from STOP to RUN mode, use [get_auto_config_status](Self::get_auto_config_status) afterwards to check whether it succeeded.
+    ///
+    /// The first filter iterations of the auto-configuration are replaced by the ones configured on the device, as the device
+    /// requires both to match, see [set_filter_iterations](Self::set_filter_iterations).
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_auto_config(
+        &mut self,
+        auto_config: &AutoConfig,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let (first_filter_iterations, _) = self.get_filter_iterations().await?;
+        self.write_auto_config(&AutoConfig {
+            first_filter_iterations,
+            ..*auto_config
+        })
+        .await
+    }
+
+    /// Sets how many samples the first filter takes (FFI), and how many of its results the second filter averages (SFI).
+    /// More iterations reduce the noise, but lengthen the response time. The first filter iterations of the auto-configuration
+    /// are updated as well, as the device requires them to match. See 5.8 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_filter_iterations(
+        &mut self,
+        first: FirstFilterIterations,
+        second: SecondFilterIterations,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let first = u8::from(first) << afe::FFI_SHIFT;
+        self.update_register(
+            Register::GlobalChargeDischargeCurrentConfig,
+            afe::FFI_MASK,
+            first,
+        )
+        .await?;
+        // AutoConfig0 places its FFI field at the same bits as AFE1
+        self.update_register(Register::AutoConfig0, afe::FFI_MASK, first)
+            .await?;
+        self.update_register(
+            Register::GlobalChargeDischargeTimeConfig,
+            afe::SFI_MASK,
+            u8::from(second) << afe::SFI_SHIFT,
+        )
+        .await
+    }
+
+    /// Sets the period between two measurements of the electrodes (ESI), which trades the response time against the power consumption.
+    /// See 5.8 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_sample_interval(
+        &mut self,
+        interval: ElectrodeSampleInterval,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.update_register(
+            Register::GlobalChargeDischargeTimeConfig,
+            afe::ESI_MASK,
+            u8::from(interval),
+        )
+        .await
+    }
+
+    /// Sets the global charge current and charge time, which is used by every electrode that does not override it.
+    /// A successful auto-configuration overrides the charge settings of the electrodes. See 5.8 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_global_charge(
+        &mut self,
+        current: ChargeCurrent,
+        time: ChargeTime,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.update_register(
+            Register::GlobalChargeDischargeCurrentConfig,
+            afe::CDC_MASK,
+            current.micro_amps(),
+        )
+        .await?;
+        self.update_register(
+            Register::GlobalChargeDischargeTimeConfig,
+            afe::CDT_MASK,
+            u8::from(time) << afe::CDT_SHIFT,
+        )
+        .await
+    }
+
+    /// Sets the charge/discharge current of a single electrode, overriding the global setting.
+    /// Passing `None` makes the electrode use the global charge current again. See 5.8 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_electrode_charge_current(
+        &mut self,
+        electrode: Electrode,
+        current: Option<ChargeCurrent>,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let value = current.map_or(0, ChargeCurrent::micro_amps);
+        self.write_register(Register::get_charge_current(electrode), value)
+            .await?;
+        Ok(())
+    }
+
+    /// Sets the charge/discharge time of a single electrode, overriding the global setting.
+    /// Passing `None` makes the electrode use the global charge time again. See 5.8 of the [Mpr121 Data Sheet](https://www.nxp.com/docs/en/data-sheet/MPR121.pdf).
+    ///
+    /// Two electrodes share one register, so this performs a read-modify-write.
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn set_electrode_charge_time(
+        &mut self,
+        electrode: Electrode,
+        time: Option<ChargeTime>,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        const CHARGE_TIME_MASK: u8 = 0b111;
+        let shift = Register::get_charge_time_shift(electrode);
+        self.update_register(
+            Register::get_charge_time(electrode),
+            CHARGE_TIME_MASK << shift,
+            time.map_or(0, u8::from) << shift,
+        )
+        .await
+    }
+
+    #[maybe_async]
+    async fn initialise_registers(
+        &mut self,
+        config: &Mpr121Config,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        //Setup Filters MHD==MaximumHalfDelta, NHD=NoiseHalfDelta
+        // Have a look at 5.5 in the data sheet for more information.
+        self.set_electrode_filter(&config.electrode_filter())
+            .await?;
+        self.set_proximity_filter(&config.proximity_filter())
+            .await?;
+
+        self.set_all_channel_thresholds(config.thresholds()).await?;
+        let (touch, release) = config.proximity_thresholds();
+        self.set_proximity_thresholds(touch, release).await?;
+        let (touch, release) = config.debounce();
+        self.set_debounce(touch, release).await?;
+
+        self.write_registers(
+            Register::GlobalChargeDischargeCurrentConfig,
+            &config.afe_register_values(),
+        )
+        .await?;
+        let (currents, times) = config.electrode_charge_register_values();
+        self.write_registers(Register::ChargeCurr0, &currents)
+            .await?;
+        self.write_registers(Register::ChargeTime0_1, &times)
+            .await?;
+        // A configuration decoded by read_config can hold different first filter iterations, AFE1 takes precedence
+        let (first_filter_iterations, _) = config.filter_iterations();
+        self.write_auto_config(&AutoConfig {
+            first_filter_iterations,
+            ..config.auto_config()
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Writes the auto-configuration registers, whose first filter iterations have to match the ones of AFE1
+    #[maybe_async]
+    async fn write_auto_config(
+        &mut self,
+        auto_config: &AutoConfig,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        let [control_0, control_1] = auto_config.register_values();
+        self.write_register(Register::AutoConfig0, control_0)
+            .await?;
+        self.write_register(Register::AutoConfig1, control_1)
+            .await?;
+        self.write_register(Register::UpSideLimit, auto_config.up_side_limit)
+            .await?;
+        self.write_register(Register::LowSideLimit, auto_config.low_side_limit)
+            .await?;
+        self.write_register(Register::TargetLevel, auto_config.target_level)
+            .await?;
+        Ok(())
+    }
+
+    /// This method will reset and verify that the correct device is on the bus, if there is a failed read/write in the process or
+    /// if the device registers do not match what is expected. It is likely that the device is not connected. Due to the nature of this function
+    /// it should only really be called once as it will reset any prexisting configurations applied
+    #[maybe_async]
+    async fn reset_verify(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.reset().await?;
+        delay.delay_us(100).await;
+        // Verify that the default registers match up
+        let register_1 = Register::GlobalChargeDischargeCurrentConfig;
+        let read_register_1_config = self.read_reg8(register_1).await?;
+        if read_register_1_config != register_1.get_initial_value() {
+            return Err(Mpr121Error::WrongDevice {
+                mismatched_register: register_1,
+                expected: register_1.get_initial_value(),
+                actual: read_register_1_config,
+            });
+        }
+        let register_2 = Register::GlobalChargeDischargeTimeConfig;
+        let read_register_2_config = self.read_reg8(register_2).await?;
+        if read_register_2_config != register_2.get_initial_value() {
+            return Err(Mpr121Error::WrongDevice {
+                mismatched_register: register_2,
+                expected: register_2.get_initial_value(),
+                actual: read_register_2_config,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<I2C: I2c, MODE> Mpr121<I2C, MODE> {
+    /// Default Threshold
+    pub const DEFAULT_TOUCH_THRESHOLD: u8 = Mpr121Config::DEFAULT_TOUCH_THRESHOLD;
+    /// Default Release
+    pub const DEFAULT_RELEASE_THRESOLD: u8 = Mpr121Config::DEFAULT_RELEASE_THRESHOLD;
+    /// The value to be written to soft reset register, to trigger a reset
+    pub(crate) const SOFT_RESET_VALUE: u8 = 0x63;
+
+    /// Reads the ECR back from the device to update the driver's copy of it. This is only necessary if
+    /// another component than this driver changes the configuration of the device.
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn resync(&mut self) -> Result<(), Mpr121Error<I2C::Error>> {
+        self.ecr = self.read_reg8(Register::Ecr).await?;
+        Ok(())
+    }
+
+    /// Returns true if the device is in RUN mode, i.e. at least one touch or proximity electrode is enabled.
+    pub fn is_running(&self) -> bool {
+        (self.ecr & ecr::RUN_MODE_MASK) != 0
+    }
+
+    /// Returns the electrode configuration the device is currently running with
+    pub fn electrode_config(&self) -> ElectrodeConfig {
+        ElectrodeConfig::from_register(self.ecr)
+    }
+
+    /// Returns the channels whose electrodes are enabled for touch sensing by the ECR
+    pub fn enabled_electrodes(&self) -> ChannelSet {
+        ChannelSet::first(self.ecr & ecr::ELE_EN_MASK)
+    }
+
+    /// Returns [Mpr121Error::ChannelDisabled] if the channel is not enabled, as the device does not measure it
+    fn check_enabled(&self, channel: Channel) -> Result<(), Mpr121Error<I2C::Error>> {
+        if self.enabled_electrodes().contains(channel) {
+            Ok(())
+        } else {
+            Err(Mpr121Error::ChannelDisabled(channel))
+        }
+    }
+
+    /// Changes the type state of the driver, the caller has to ensure that the device is in this mode
+    fn into_mode<NEW>(self) -> Mpr121<I2C, NEW> {
+        Mpr121 {
+            i2c: self.i2c,
+            addr: self.addr,
+            ecr: self.ecr,
+            run_ecr: self.run_ecr,
+            touch_status: self.touch_status,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Returns true if over-current is detected by the device.
+    /// In that case you probably have to check your circuit
+    ///
+    /// In the event of an error [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn is_over_current_set(&mut self) -> Result<bool, Mpr121Error<I2C::Error>> {
+        const OVER_CURRENT_PROTECTION_FLAG_MASK: u8 = 0b1 << 7;
+        let read = self.read_reg8(Register::TouchStatus8_11).await?;
+        //If bit D7 is set, we have OVCF
+        Ok((read & (OVER_CURRENT_PROTECTION_FLAG_MASK)) > 0)
+    }
+
+    /// Reads the *touched* state of all channels. Returns the [ChannelSet] of touched channels, or, if only one sensor's value is
+    /// needed, use [get_touch_state](Self::get_sensor_touch).
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_touched(&mut self) -> Result<ChannelSet, Mpr121Error<I2C::Error>> {
+        //the upper four bits are ELEPROX and the over current flag
+        let unmasked = self.read_reg16(Register::TouchStatus0_7).await?;
+        Ok(ChannelSet::from_bits(unmasked))
+    }
+
+    ///Returns the touch state of the given sensor.
+    ///
+    /// If the channel is not enabled, [Mpr121Error::ChannelDisabled] is returned.
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_sensor_touch(
+        &mut self,
+        channel: Channel,
+    ) -> Result<bool, Mpr121Error<I2C::Error>> {
+        self.check_enabled(channel)?;
+        let result = self.get_touched().await?;
+        Ok(result.contains(channel))
+    }
+
+    /// Reads the (touch, release) threshold of the channel.
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_channel_thresholds(
+        &mut self,
+        channel: Channel,
+    ) -> Result<(u8, u8), Mpr121Error<I2C::Error>> {
+        let mut values = [0u8; 2];
+        self.read_registers(Register::get_threshold_register(channel), &mut values)
+            .await?;
+        Ok((values[0], values[1]))
+    }
+
+    /// Reads the (touch, release) thresholds of all channels in a single transaction, stored at the index of the channel.
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_all_channel_thresholds(
+        &mut self,
+    ) -> Result<[(u8, u8); Channel::NUM_CHANNELS as usize], Mpr121Error<I2C::Error>> {
+        let mut values = [0u8; 2 * Channel::NUM_CHANNELS as usize];
+        self.read_registers(Register::get_threshold_register(Channel::Zero), &mut values)
+            .await?;
+        let mut result = [(0u8, 0u8); Channel::NUM_CHANNELS as usize];
+        for (thresholds, value) in result.iter_mut().zip(values.chunks_exact(2)) {
+            *thresholds = (value[0], value[1]);
+        }
+        Ok(result)
+    }
+
+    /// Reads the configuration registers (0x2B to 0x7F) in a single transaction and decodes them into a [Mpr121Config],
+    /// e.g. to verify that no other component reconfigured the device. Comparing the result with the configuration passed
+    /// to `Mpr121::with_config` only succeeds if the auto-configuration is disabled, as it overrides the charge settings
+    /// of the electrodes. In STOP mode the electrode configuration reports no enabled electrodes.
+    ///
+    /// The first filter iterations of AFE1 are reported by [filter_iterations](Mpr121Config::filter_iterations), the ones of
+    /// the auto-configuration by [auto_config](Mpr121Config::auto_config), so a mismatch between both is visible.
+    ///
+    /// If the global charge current or charge time holds an invalid value, [Mpr121Error::DataConversionError] is returned.
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn read_config(&mut self) -> Result<Mpr121Config, Mpr121Error<I2C::Error>> {
+        let mut registers = [0u8; Mpr121Config::LENGTH];
+        self.read_registers(Mpr121Config::FIRST_REGISTER, &mut registers)
+            .await?;
+        Mpr121Config::from_registers(&registers).map_err(Mpr121Error::DataConversionError)
+    }
+
+    /// Reads the filtered data from touch channels. Noise gets filtered out by the
+    /// chip. See 5.3 in the data sheet.
+    ///
+    /// Note that the resulting value is only 10bit wide.
+    ///
+    /// If the channel is not enabled, [Mpr121Error::ChannelDisabled] is returned.
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_filtered(&mut self, channel: Channel) -> Result<u16, Mpr121Error<I2C::Error>> {
+        self.check_enabled(channel)?;
+        let register = Register::get_filtered_data_msb(channel);
+        let result = self.read_reg16(register).await?;
+        Ok(result)
+    }
+
+    /// Reads the filtered data of all touch channels in a single transaction, indexed by their [Channel],
+    /// see [get_filtered](Self::get_filtered).
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_all_filtered(
+        &mut self,
+    ) -> Result<ChannelValues<u16>, Mpr121Error<I2C::Error>> {
+        let mut buffer = [0u8; 2 * Channel::NUM_CHANNELS as usize];
+        self.read_registers(Register::FiltData0MSB, &mut buffer)
+            .await?;
+        let mut result = [0u16; Channel::NUM_CHANNELS as usize];
+        for (value, bytes) in result.iter_mut().zip(buffer.chunks_exact(2)) {
+            *value = u16::from_le_bytes([bytes[0], bytes[1]]);
+        }
+        Ok(result.into())
+    }
+
+    /// Reads the baseline of the channel. The device only exposes the 8 MSB of its internal 10bit baseline, which are
+    /// left shifted by two bits, so the result is comparable with the 10bit [filtered data](Self::get_filtered). See 5.6 in the data sheet.
+    ///
+    /// If the channel is not enabled, [Mpr121Error::ChannelDisabled] is returned.
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_baseline(&mut self, channel: Channel) -> Result<u16, Mpr121Error<I2C::Error>> {
+        self.check_enabled(channel)?;
+        let read = self.read_reg8(Register::get_baseline(channel)).await?;
+        Ok(u16::from(read) << 2)
+    }
+
+    /// Reads the baseline of all touch channels in a single transaction, indexed by their [Channel],
+    /// see [get_baseline](Self::get_baseline).
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_all_baselines(
+        &mut self,
+    ) -> Result<ChannelValues<u16>, Mpr121Error<I2C::Error>> {
+        let mut buffer = [0u8; Channel::NUM_CHANNELS as usize];
+        self.read_registers(Register::BaseLine0, &mut buffer)
+            .await?;
+        Ok(buffer.map(|value| u16::from(value) << 2).into())
+    }
+
+    /// Reads the difference between the baseline and the filtered data of the channel, which the touch and release thresholds
+    /// are compared against. Touching an electrode lowers the filtered data, so a touch shows up as a positive delta.
+    /// Both values are read in a single transaction, so they belong to the same measurement.
+    ///
+    /// If the channel is not enabled, [Mpr121Error::ChannelDisabled] is returned.
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_delta(&mut self, channel: Channel) -> Result<i16, Mpr121Error<I2C::Error>> {
+        self.check_enabled(channel)?;
+        // ELE0 has the largest distance between its filtered data and its baseline
+        const LENGTH: usize =
+            (Register::BaseLine0 as usize) - (Register::FiltData0MSB as usize) + 1;
+        let filtered = Register::get_filtered_data_msb(channel);
+        let baseline = Register::get_baseline(channel);
+        let length = usize::from(u8::from(baseline) - u8::from(filtered)) + 1;
+        let mut buffer = [0u8; LENGTH];
+        self.read_registers(filtered, &mut buffer[..length]).await?;
+        let filtered = u16::from_le_bytes([buffer[0], buffer[1]]) & FILTERED_DATA_MASK;
+        let baseline = u16::from(buffer[length - 1]) << 2;
+        Ok(baseline as i16 - filtered as i16)
+    }
+
+    /// Reads the out-of-range status of all electrodes, together with the auto-configuration failure flags.
+    /// Electrodes which are shorted or not connected are reported as out of range by the auto-configuration.
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn out_of_range(&mut self) -> Result<OutOfRangeStatus, Mpr121Error<I2C::Error>> {
+        let read = self.read_reg16(Register::OORStatus).await?;
+        Ok(OutOfRangeStatus::from_registers(read))
+    }
+
+    /// Reads whether the last auto-configuration or auto-reconfiguration failed. A failed auto-configuration
+    /// leaves the affected electrodes without valid charge settings, which usually shows up as electrodes that never trigger.
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_auto_config_status(
+        &mut self,
+    ) -> Result<AutoConfigStatus, Mpr121Error<I2C::Error>> {
+        const AUTO_CONFIG_FAIL_FLAG_MASK: u8 = 0b1 << 7;
+        const AUTO_RECONFIG_FAIL_FLAG_MASK: u8 = 0b1 << 6;
+        let read = self.read_reg8(Register::EleproxOORStatus).await?;
+        Ok(AutoConfigStatus {
+            auto_config_failed: (read & AUTO_CONFIG_FAIL_FLAG_MASK) > 0,
+            auto_reconfig_failed: (read & AUTO_RECONFIG_FAIL_FLAG_MASK) > 0,
+        })
+    }
+
+    /// Reads the charge/discharge current of a single electrode. Returns `None` if the electrode uses the global charge current.
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_electrode_charge_current(
+        &mut self,
+        electrode: Electrode,
+    ) -> Result<Option<ChargeCurrent>, Mpr121Error<I2C::Error>> {
+        let read = self
+            .read_reg8(Register::get_charge_current(electrode))
+            .await?;
+        Ok(ChargeCurrent::new(read & ChargeCurrent::MAX_MICRO_AMPS))
+    }
+
+    /// Reads the charge/discharge time of a single electrode. Returns `None` if the electrode uses the global charge time.
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_electrode_charge_time(
+        &mut self,
+        electrode: Electrode,
+    ) -> Result<Option<ChargeTime>, Mpr121Error<I2C::Error>> {
+        const CHARGE_TIME_MASK: u8 = 0b111;
+        let register = Register::get_charge_time(electrode);
+        let read = self.read_reg8(register).await?;
+        let bits = (read >> Register::get_charge_time_shift(electrode)) & CHARGE_TIME_MASK;
+        // A value of zero selects the global charge time, every other value is a valid ChargeTime
+        Ok(ChargeTime::try_from(bits).ok())
+    }
+
+    /// Reads the (first, second) filter iterations, see [set_filter_iterations](Mpr121::set_filter_iterations).
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_filter_iterations(
+        &mut self,
+    ) -> Result<(FirstFilterIterations, SecondFilterIterations), Mpr121Error<I2C::Error>> {
+        let [afe_1, afe_2] = self.read_afe().await?;
+        Ok((
+            FirstFilterIterations::try_from((afe_1 & afe::FFI_MASK) >> afe::FFI_SHIFT)
+                .expect("Every two bit value is a valid first filter iterations"),
+            SecondFilterIterations::try_from((afe_2 & afe::SFI_MASK) >> afe::SFI_SHIFT)
+                .expect("Every two bit value is a valid second filter iterations"),
+        ))
+    }
+
+    /// Reads the period between two measurements of the electrodes, see [set_sample_interval](Mpr121::set_sample_interval).
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_sample_interval(
+        &mut self,
+    ) -> Result<ElectrodeSampleInterval, Mpr121Error<I2C::Error>> {
+        let read = self
+            .read_reg8(Register::GlobalChargeDischargeTimeConfig)
+            .await?;
+        Ok(ElectrodeSampleInterval::try_from(read & afe::ESI_MASK)
+            .expect("Every three bit value is a valid sample interval"))
+    }
+
+    /// Reads the global (current, time) charge settings, see [set_global_charge](Mpr121::set_global_charge).
+    ///
+    /// If the device holds a charge current or charge time of zero, [Mpr121Error::DataConversionError] is returned.
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_global_charge(
+        &mut self,
+    ) -> Result<(ChargeCurrent, ChargeTime), Mpr121Error<I2C::Error>> {
+        let [afe_1, afe_2] = self.read_afe().await?;
+        let current = ChargeCurrent::new(afe_1 & afe::CDC_MASK).ok_or(
+            Mpr121Error::DataConversionError(Register::GlobalChargeDischargeCurrentConfig),
+        )?;
+        let time =
+            ChargeTime::try_from((afe_2 & afe::CDT_MASK) >> afe::CDT_SHIFT).map_err(|_| {
+                Mpr121Error::DataConversionError(Register::GlobalChargeDischargeTimeConfig)
+            })?;
+        Ok((current, time))
+    }
+
+    /// Reads the AFE1 and AFE2 registers in a single transaction
+    #[maybe_async]
+    async fn read_afe(&mut self) -> Result<[u8; 2], Mpr121Error<I2C::Error>> {
+        let mut values = [0u8; 2];
+        self.read_registers(Register::GlobalChargeDischargeCurrentConfig, &mut values)
+            .await?;
+        Ok(values)
+    }
+
+    /// Returns which electrodes are currently combined into the ELEPROX electrode.
+    pub fn get_proximity_mode(&self) -> ProximityMode {
+        self.electrode_config().proximity_mode
+    }
+
+    /// Returns the calibration lock the device is currently running with.
+    pub fn get_calibration_lock(&self) -> CalibrationLock {
+        self.electrode_config().calibration_lock
+    }
+
+    /// Reads the filtered data of the ELEPROX electrode. Like [get_filtered](Self::get_filtered) the value is only 10bit wide.
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_proximity_filtered(&mut self) -> Result<u16, Mpr121Error<I2C::Error>> {
+        let result = self.read_reg16(Register::EleproxFiltDataMSB).await?;
+        Ok(result)
+    }
+
+    /// Reads the baseline of the ELEPROX electrode, left shifted like [get_baseline](Self::get_baseline).
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_proximity_baseline(&mut self) -> Result<u16, Mpr121Error<I2C::Error>> {
+        let result = self.read_reg8(Register::EleproxBaseLine).await?;
+        Ok(u16::from(result) << 2)
+    }
+
+    /// Returns true if the ELEPROX electrode detects an object in proximity.
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn is_proximity_detected(&mut self) -> Result<bool, Mpr121Error<I2C::Error>> {
+        const ELEPROX_TOUCH_STATUS_MASK: u8 = 0b1 << 4;
+        let read = self.read_reg8(Register::TouchStatus8_11).await?;
+        //Bit D4 of the second status register is the touch status of ELEPROX
+        Ok((read & ELEPROX_TOUCH_STATUS_MASK) > 0)
+    }
+}
@@ -0,0 +1,16 @@
+use super::{maybe_async, I2c, Mpr121};
+use crate::{registers::*, snapshot::Mpr121Snapshot, Mpr121Error};
+
+impl<I2C: I2c, MODE> Mpr121<I2C, MODE> {
+    /// Reads the touch status, the out-of-range status, the filtered data and the baselines of all electrodes
+    /// in a single transaction, see [Mpr121Snapshot].
+    ///
+    /// Otherwise [Mpr121Error] is returned
+    #[maybe_async]
+    pub async fn get_snapshot(&mut self) -> Result<Mpr121Snapshot, Mpr121Error<I2C::Error>> {
+        let mut buffer = [0u8; Mpr121Snapshot::LENGTH];
+        self.read_registers(Register::TouchStatus0_7, &mut buffer)
+            .await?;
+        Ok(Mpr121Snapshot::from_registers(&buffer))
+    }
+}
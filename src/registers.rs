@@ -1,8 +1,9 @@
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-use crate::Channel;
+use crate::{gpio::GpioPin, Channel, Electrode};
 #[repr(u8)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, IntoPrimitive, TryFromPrimitive, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Register {
     TouchStatus0_7 = 0x00,
     TouchStatus8_11 = 0x01,
@@ -32,8 +33,8 @@ pub enum Register {
     FiltData10LSB = 0x19,
     FiltData11MSB = 0x1A,
     FiltData11LSB = 0x1B,
-    // EleproxFiltDataMSB = 0x1C,
-    // EleproxFiltDataLSB = 0x1D,
+    EleproxFiltDataMSB = 0x1C,
+    EleproxFiltDataLSB = 0x1D,
     BaseLine0 = 0x1E,
     BaseLine1 = 0x1F,
     BaseLine2 = 0x20,
@@ -58,6 +59,17 @@ pub enum Register {
     NoiseHalfDeltaTouched = 0x33,
     NoiseCountLimitTouched = 0x34,
     FilterDelayCountLimitTouched = 0x35,
+    ProxMaximumHalfDeltaRising = 0x36,
+    ProxNoiseHalfDeltaRising = 0x37,
+    ProxNoiseCountLimitRising = 0x38,
+    ProxFilterDelayCountLimitRising = 0x39,
+    ProxMaximumHalfDeltaFalling = 0x3A,
+    ProxNoiseHalfDeltaFalling = 0x3B,
+    ProxNoiseCountLimitFalling = 0x3C,
+    ProxFilterDelayCountLimitFalling = 0x3D,
+    ProxNoiseHalfDeltaTouched = 0x3E,
+    ProxNoiseCountLimitTouched = 0x3F,
+    ProxFilterDelayCountLimitTouched = 0x40,
     TouchThreshold0 = 0x41,
     ReleaseThreshold0 = 0x42,
     TouchThreshold1 = 0x43,
@@ -82,12 +94,32 @@ pub enum Register {
     ReleaseThreshold10 = 0x56,
     TouchThreshold11 = 0x57,
     ReleaseThreshold11 = 0x58,
+    EleproxTouchThreshold = 0x59,
+    EleproxReleaseThreshold = 0x5A,
     Debounce = 0x5B,
     GlobalChargeDischargeCurrentConfig = 0x5C,
     GlobalChargeDischargeTimeConfig = 0x5D,
-    ChargeCurr0 = 0x5F,
-    ChargeTime1 = 0x6C,
     Ecr = 0x5E,
+    ChargeCurr0 = 0x5F,
+    ChargeCurr1 = 0x60,
+    ChargeCurr2 = 0x61,
+    ChargeCurr3 = 0x62,
+    ChargeCurr4 = 0x63,
+    ChargeCurr5 = 0x64,
+    ChargeCurr6 = 0x65,
+    ChargeCurr7 = 0x66,
+    ChargeCurr8 = 0x67,
+    ChargeCurr9 = 0x68,
+    ChargeCurr10 = 0x69,
+    ChargeCurr11 = 0x6A,
+    ChargeCurrEleprox = 0x6B,
+    ChargeTime0_1 = 0x6C,
+    ChargeTime2_3 = 0x6D,
+    ChargeTime4_5 = 0x6E,
+    ChargeTime6_7 = 0x6F,
+    ChargeTime8_9 = 0x70,
+    ChargeTime10_11 = 0x71,
+    ChargeTimeEleprox = 0x72,
     AutoConfig0 = 0x7B,
     AutoConfig1 = 0x7C,
     UpSideLimit = 0x7D,
@@ -104,6 +136,12 @@ pub enum Register {
     GpioDataSet = 0x78,
     GpioDataClear = 0x79,
     GpioDataToggle = 0x7A,
+
+    // PWM Registers (0x81-0x84)
+    Pwm0 = 0x81,
+    Pwm1 = 0x82,
+    Pwm2 = 0x83,
+    Pwm3 = 0x84,
 }
 
 impl Register {
@@ -178,6 +216,69 @@ impl Register {
         }
     }
 
+    /// Returns the charge current register associated with the electrode
+    pub fn get_charge_current(electrode: Electrode) -> Register {
+        match electrode {
+            Electrode::Channel(Channel::Zero) => Register::ChargeCurr0,
+            Electrode::Channel(Channel::One) => Register::ChargeCurr1,
+            Electrode::Channel(Channel::Two) => Register::ChargeCurr2,
+            Electrode::Channel(Channel::Three) => Register::ChargeCurr3,
+            Electrode::Channel(Channel::Four) => Register::ChargeCurr4,
+            Electrode::Channel(Channel::Five) => Register::ChargeCurr5,
+            Electrode::Channel(Channel::Six) => Register::ChargeCurr6,
+            Electrode::Channel(Channel::Seven) => Register::ChargeCurr7,
+            Electrode::Channel(Channel::Eight) => Register::ChargeCurr8,
+            Electrode::Channel(Channel::Nine) => Register::ChargeCurr9,
+            Electrode::Channel(Channel::Ten) => Register::ChargeCurr10,
+            Electrode::Channel(Channel::Eleven) => Register::ChargeCurr11,
+            Electrode::Eleprox => Register::ChargeCurrEleprox,
+        }
+    }
+
+    /// Returns the charge time register associated with the electrode. Two electrodes share one register,
+    /// use [get_charge_time_shift](Self::get_charge_time_shift) to find the field of the electrode.
+    pub fn get_charge_time(electrode: Electrode) -> Register {
+        match electrode {
+            Electrode::Channel(Channel::Zero | Channel::One) => Register::ChargeTime0_1,
+            Electrode::Channel(Channel::Two | Channel::Three) => Register::ChargeTime2_3,
+            Electrode::Channel(Channel::Four | Channel::Five) => Register::ChargeTime4_5,
+            Electrode::Channel(Channel::Six | Channel::Seven) => Register::ChargeTime6_7,
+            Electrode::Channel(Channel::Eight | Channel::Nine) => Register::ChargeTime8_9,
+            Electrode::Channel(Channel::Ten | Channel::Eleven) => Register::ChargeTime10_11,
+            Electrode::Eleprox => Register::ChargeTimeEleprox,
+        }
+    }
+
+    /// Returns the bit offset of the electrode's 3 bit field within its [charge time register](Self::get_charge_time).
+    /// Even electrodes use the lower bits, odd electrodes the upper bits. See Datasheet 5.8
+    pub fn get_charge_time_shift(electrode: Electrode) -> u8 {
+        match electrode {
+            Electrode::Channel(channel) if u8::from(channel) % 2 == 1 => 4,
+            _ => 0,
+        }
+    }
+
+    /// Returns the PWM duty register associated with the GPIO pin. Two pins share one register,
+    /// use [get_pwm_shift](Self::get_pwm_shift) to find the field of the pin.
+    pub fn get_pwm(pin: GpioPin) -> Register {
+        match pin {
+            GpioPin::Four | GpioPin::Five => Register::Pwm0,
+            GpioPin::Six | GpioPin::Seven => Register::Pwm1,
+            GpioPin::Eight | GpioPin::Nine => Register::Pwm2,
+            GpioPin::Ten | GpioPin::Eleven => Register::Pwm3,
+        }
+    }
+
+    /// Returns the bit offset of the pin's 4 bit field within its [PWM register](Self::get_pwm).
+    /// Even pins use the lower bits, odd pins the upper bits.
+    pub fn get_pwm_shift(pin: GpioPin) -> u8 {
+        if u8::from(pin) % 2 == 1 {
+            4
+        } else {
+            0
+        }
+    }
+
     /// Some registers require for the sensor to be in stop mode before they can be accessed
     pub fn require_stop(&self) -> bool {
         !matches!(
@@ -192,6 +293,11 @@ impl Register {
                 | Self::GpioDataSet
                 | Self::GpioDataClear
                 | Self::GpioDataToggle
+                | Self::Pwm0
+                | Self::Pwm1
+                | Self::Pwm2
+                | Self::Pwm3
+                | Self::SoftReset
         )
     }
 
@@ -205,6 +311,72 @@ impl Register {
     }
 }
 
+/// The filtered data is 10 bits wide, the upper bits of its MSB register are reserved
+pub const FILTERED_DATA_MASK: u16 = 0x3ff;
+
+/// The baseline filter registers of the 12 touch electrodes, in the order of [BaselineFilter::register_values](crate::BaselineFilter::register_values).
+/// See Datasheet 5.5
+pub const ELECTRODE_FILTER_REGISTERS: [Register; 11] = [
+    Register::MaximumHalfDeltaRising,
+    Register::NoiseHalfDataRising,
+    Register::NoiseCountLimitRising,
+    Register::FilterDelayCountLimitRising,
+    Register::MaximmHalfDeltaFalling,
+    Register::NoiseHalfDeltaFalling,
+    Register::NoiseCountLimitFalling,
+    Register::FilterDelayCountFalling,
+    Register::NoiseHalfDeltaTouched,
+    Register::NoiseCountLimitTouched,
+    Register::FilterDelayCountLimitTouched,
+];
+
+/// The baseline filter registers of the ELEPROX electrode, in the order of [BaselineFilter::register_values](crate::BaselineFilter::register_values).
+/// See Datasheet 5.5
+pub const PROXIMITY_FILTER_REGISTERS: [Register; 11] = [
+    Register::ProxMaximumHalfDeltaRising,
+    Register::ProxNoiseHalfDeltaRising,
+    Register::ProxNoiseCountLimitRising,
+    Register::ProxFilterDelayCountLimitRising,
+    Register::ProxMaximumHalfDeltaFalling,
+    Register::ProxNoiseHalfDeltaFalling,
+    Register::ProxNoiseCountLimitFalling,
+    Register::ProxFilterDelayCountLimitFalling,
+    Register::ProxNoiseHalfDeltaTouched,
+    Register::ProxNoiseCountLimitTouched,
+    Register::ProxFilterDelayCountLimitTouched,
+];
+
+/// Bit fields of the Electrode Configuration Register. See Datasheet 5.11
+pub mod ecr {
+    /// ELE_EN, the number of enabled touch electrodes
+    pub const ELE_EN_MASK: u8 = 0b0000_1111;
+    /// ELEPROX_EN, the electrodes combined for proximity detection
+    pub const ELEPROX_EN_SHIFT: u8 = 4;
+    pub const ELEPROX_EN_MASK: u8 = 0b11 << ELEPROX_EN_SHIFT;
+    /// CL, the calibration lock
+    pub const CL_SHIFT: u8 = 6;
+    pub const CL_MASK: u8 = 0b11 << CL_SHIFT;
+    /// The device is in STOP mode if neither touch nor proximity electrodes are enabled
+    pub const RUN_MODE_MASK: u8 = ELE_EN_MASK | ELEPROX_EN_MASK;
+}
+
+/// Bit fields of the analog front end registers AFE1 (global charge current) and AFE2 (global charge time). See Datasheet 5.8
+pub mod afe {
+    /// FFI, the first filter iterations in AFE1
+    pub const FFI_SHIFT: u8 = 6;
+    pub const FFI_MASK: u8 = 0b11 << FFI_SHIFT;
+    /// CDC, the global charge current in AFE1
+    pub const CDC_MASK: u8 = 0b0011_1111;
+    /// CDT, the global charge time in AFE2
+    pub const CDT_SHIFT: u8 = 5;
+    pub const CDT_MASK: u8 = 0b111 << CDT_SHIFT;
+    /// SFI, the second filter iterations in AFE2
+    pub const SFI_SHIFT: u8 = 3;
+    pub const SFI_MASK: u8 = 0b11 << SFI_SHIFT;
+    /// ESI, the electrode sample interval in AFE2
+    pub const ESI_MASK: u8 = 0b111;
+}
+
 /// Limit Values for the Up and Low Side boundary checking on the electroludes. See Datasheet Page 19 for more details
 pub mod limits {
     const VDD_VALUE: f32 = 3.3;
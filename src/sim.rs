@@ -0,0 +1,311 @@
+//! A software model of the MPR121 on register level, which implements the I2C traits so the driver can be tested without hardware.
+//!
+//! The simulator models the register file with its soft reset defaults, the auto-incrementing register pointer,
+//! which wraps around at the end of the data and of the configuration registers, and the write restrictions of RUN mode, in which only the ECR, the GPIO and PWM registers and the soft reset register accept writes.
+//! Like on the device, other writes in RUN mode are ignored, use [rejected_writes](Mpr121Sim::rejected_writes) to detect them.
+//! It does not measure anything, the touch status and electrode data are set through the methods of [Mpr121Sim] instead.
+//! Changes of the touch status assert the IRQ, until the touch status is read.
+//!
+//! As the driver takes ownership of its I2C bus, the I2C traits are implemented for `&Mpr121Sim`:
+//! ```
+//! use mpr121_hal::mpr121::blocking::Mpr121;
+//! use mpr121_hal::sim::Mpr121Sim;
+//! use mpr121_hal::{Channel, Electrode, Mpr121Address};
+//!
+//! struct NoDelay;
+//! impl embedded_hal::delay::DelayNs for NoDelay {
+//!     fn delay_ns(&mut self, _ns: u32) {}
+//! }
+//!
+//! let sim = Mpr121Sim::new(Mpr121Address::Default);
+//! let mut sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay).unwrap();
+//! sim.set_touched(Electrode::Channel(Channel::Three), true);
+//! assert!(sensor.get_sensor_touch(Channel::Three).unwrap());
+//! ```
+use core::cell::RefCell;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource, Operation};
+
+use crate::{registers::*, Electrode, Mpr121Address};
+
+/// The number of registers of the device, from the touch status (0x00) up to the last PWM register (0x84)
+const REGISTER_COUNT: usize = (Register::Pwm3 as usize) + 1;
+
+/// The value written to the soft reset register to trigger a reset
+const SOFT_RESET_VALUE: u8 = 0x63;
+/// The bit of the touch status registers that indicates an over-current on REXT
+const OVER_CURRENT_BIT: u8 = 15;
+/// The bit of the touch and out-of-range status registers that belongs to the ELEPROX electrode
+const ELEPROX_BIT: u8 = 12;
+
+/// A simulated MPR121 at the given address. Transactions to any other address are not acknowledged.
+pub struct Mpr121Sim {
+    address: u8,
+    state: RefCell<SimState>,
+}
+
+struct SimState {
+    registers: [u8; REGISTER_COUNT],
+    pointer: u8,
+    rejected_writes: usize,
+    /// The over-current condition persists through a soft reset
+    over_current: bool,
+    /// The IRQ pin is pulled low on a change of the touch status, until the touch status is read
+    irq: bool,
+}
+
+impl SimState {
+    fn reset(&mut self) {
+        for (address, value) in self.registers.iter_mut().enumerate() {
+            *value = Register::try_from(address as u8)
+                .map(|reg| reg.get_initial_value())
+                .unwrap_or(0);
+        }
+        let over_current = self.over_current;
+        self.set_bit(Register::TouchStatus0_7, OVER_CURRENT_BIT, over_current);
+    }
+
+    fn is_running(&self) -> bool {
+        (self.registers[usize::from(u8::from(Register::Ecr))] & ecr::RUN_MODE_MASK) != 0
+    }
+
+    fn read(&mut self) -> u8 {
+        if self.pointer <= u8::from(Register::TouchStatus8_11) {
+            self.irq = false;
+        }
+        let value = self
+            .registers
+            .get(usize::from(self.pointer))
+            .copied()
+            .unwrap_or(0);
+        self.advance_pointer();
+        value
+    }
+
+    fn write(&mut self, value: u8) {
+        let address = self.pointer;
+        self.advance_pointer();
+        let Ok(reg) = Register::try_from(address) else {
+            return;
+        };
+        // The status and filtered data registers are read only
+        if address < u8::from(Register::BaseLine0) || (reg.require_stop() && self.is_running()) {
+            self.rejected_writes += 1;
+            return;
+        }
+        match reg {
+            Register::SoftReset => {
+                if value == SOFT_RESET_VALUE {
+                    self.reset();
+                }
+            }
+            Register::GpioDataSet => {
+                self.registers[usize::from(u8::from(Register::GpioData))] |= value
+            }
+            Register::GpioDataClear => {
+                self.registers[usize::from(u8::from(Register::GpioData))] &= !value
+            }
+            Register::GpioDataToggle => {
+                self.registers[usize::from(u8::from(Register::GpioData))] ^= value
+            }
+            _ => self.registers[usize::from(address)] = value,
+        }
+    }
+
+    /// Increments the register pointer. Like on the device, bursts wrap around within the data registers (0x00 to 0x2A)
+    /// and within the configuration registers (0x2B to 0x7F).
+    fn advance_pointer(&mut self) {
+        const LAST_DATA_REGISTER: u8 = Register::MaximumHalfDeltaRising as u8 - 1;
+        const LAST_CONFIG_REGISTER: u8 = Register::TargetLevel as u8;
+        self.pointer = match self.pointer {
+            LAST_DATA_REGISTER => u8::from(Register::TouchStatus0_7),
+            LAST_CONFIG_REGISTER => u8::from(Register::MaximumHalfDeltaRising),
+            pointer => pointer.wrapping_add(1),
+        };
+    }
+
+    fn set_bit(&mut self, reg: Register, bit: u8, set: bool) {
+        let value = &mut self.registers[usize::from(u8::from(reg)) + usize::from(bit / 8)];
+        if set {
+            *value |= 1 << (bit % 8);
+        } else {
+            *value &= !(1 << (bit % 8));
+        }
+    }
+
+    /// Updates a bit of the touch status registers, which asserts the IRQ if the status changes
+    fn set_touch_status_bit(&mut self, bit: u8, set: bool) {
+        let index = usize::from(u8::from(Register::TouchStatus0_7)) + usize::from(bit / 8);
+        let previous = self.registers[index];
+        self.set_bit(Register::TouchStatus0_7, bit, set);
+        self.irq |= previous != self.registers[index];
+    }
+}
+
+impl Mpr121Sim {
+    /// Creates a simulated device at the given address, with all registers at their reset values
+    pub fn new(address: Mpr121Address) -> Self {
+        let mut state = SimState {
+            registers: [0; REGISTER_COUNT],
+            pointer: 0,
+            rejected_writes: 0,
+            over_current: false,
+            irq: false,
+        };
+        state.reset();
+        Mpr121Sim {
+            address: address.into(),
+            state: RefCell::new(state),
+        }
+    }
+
+    /// Returns the value of the register at the given address
+    pub fn register(&self, address: u8) -> u8 {
+        self.state
+            .borrow()
+            .registers
+            .get(usize::from(address))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Sets the register at the given address, regardless of the mode of the device and whether the register is read only
+    pub fn set_register(&self, address: u8, value: u8) {
+        if let Some(register) = self
+            .state
+            .borrow_mut()
+            .registers
+            .get_mut(usize::from(address))
+        {
+            *register = value;
+        }
+    }
+
+    /// Returns true if the ECR enables any electrode, i.e. the device is in RUN mode
+    pub fn is_running(&self) -> bool {
+        self.state.borrow().is_running()
+    }
+
+    /// Returns true if the active low IRQ pin is asserted, i.e. the touch status changed and was not read since
+    pub fn is_irq_asserted(&self) -> bool {
+        self.state.borrow().irq
+    }
+
+    /// Returns how many writes were ignored, because the register is read only or requires STOP mode
+    pub fn rejected_writes(&self) -> usize {
+        self.state.borrow().rejected_writes
+    }
+
+    /// Sets the touch status of the electrode
+    pub fn set_touched(&self, electrode: Electrode, touched: bool) {
+        self.state
+            .borrow_mut()
+            .set_touch_status_bit(electrode_bit(electrode), touched);
+    }
+
+    /// Sets or clears the over-current condition on REXT, which is reported by the over-current flag, even after a soft reset
+    pub fn set_over_current(&self, over_current: bool) {
+        let mut state = self.state.borrow_mut();
+        state.over_current = over_current;
+        state.set_touch_status_bit(OVER_CURRENT_BIT, over_current);
+    }
+
+    /// Sets the out-of-range status of the electrode
+    pub fn set_out_of_range(&self, electrode: Electrode, out_of_range: bool) {
+        self.state.borrow_mut().set_bit(
+            Register::OORStatus,
+            electrode_bit(electrode),
+            out_of_range,
+        );
+    }
+
+    /// Sets the 10 bit filtered data of the electrode
+    pub fn set_filtered(&self, electrode: Electrode, value: u16) {
+        let reg = match electrode {
+            Electrode::Channel(channel) => Register::get_filtered_data_msb(channel),
+            Electrode::Eleprox => Register::EleproxFiltDataMSB,
+        };
+        let index = usize::from(u8::from(reg));
+        let [low, high] = (value & 0x3ff).to_le_bytes();
+        let mut state = self.state.borrow_mut();
+        state.registers[index] = low;
+        state.registers[index + 1] = high;
+    }
+
+    /// Sets the baseline of the electrode, of which the device only stores the 8 MSB of the 10 bit value
+    pub fn set_baseline(&self, electrode: Electrode, value: u16) {
+        let reg = match electrode {
+            Electrode::Channel(channel) => Register::get_baseline(channel),
+            Electrode::Eleprox => Register::EleproxBaseLine,
+        };
+        self.state.borrow_mut().registers[usize::from(u8::from(reg))] =
+            ((value & 0x3ff) >> 2) as u8;
+    }
+
+    /// Processes the operations of one I2C transaction. Writes set the register pointer with their first byte,
+    /// all further bytes of writes and reads access the register at the pointer, which is advanced after each access.
+    fn transaction(&self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), ErrorKind> {
+        if address != self.address {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        let mut state = self.state.borrow_mut();
+        // Adjacent writes are sent without a repeated start, so only the first byte after a start sets the pointer
+        let mut is_writing = false;
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    let mut values = bytes.iter();
+                    if !is_writing {
+                        if let Some(&pointer) = values.next() {
+                            state.pointer = pointer;
+                            is_writing = true;
+                        }
+                    }
+                    for &value in values {
+                        state.write(value);
+                    }
+                }
+                Operation::Read(buffer) => {
+                    is_writing = false;
+                    for value in buffer.iter_mut() {
+                        *value = state.read();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the bit of the electrode in the touch and out-of-range status registers
+fn electrode_bit(electrode: Electrode) -> u8 {
+    match electrode {
+        Electrode::Channel(channel) => channel.into(),
+        Electrode::Eleprox => ELEPROX_BIT,
+    }
+}
+
+impl ErrorType for &Mpr121Sim {
+    type Error = ErrorKind;
+}
+
+impl embedded_hal::i2c::I2c for &Mpr121Sim {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        Mpr121Sim::transaction(self, address, operations)
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for &Mpr121Sim {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        Mpr121Sim::transaction(self, address, operations)
+    }
+}
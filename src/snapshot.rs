@@ -0,0 +1,101 @@
+//! A coherent view of the sensor state, read in a single I2C transaction.
+//!
+//! Reading the touch status, the filtered data and the baselines through separate calls allows the state of the sensor to change
+//! in between, e.g. a channel is reported as touched while its delta is already below the touch threshold.
+//! A [Mpr121Snapshot] reads all of these registers (0x00 to 0x2A) in one burst, so all values belong to the same measurement.
+use crate::{registers::*, Channel, ChannelSet, ChannelValues, OutOfRangeStatus};
+
+/// The state of all electrodes at one point in time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Mpr121Snapshot {
+    /// The touched channels, see `Mpr121::get_touched`
+    pub touched: ChannelSet,
+    /// The ELEPROX electrode detects an object in proximity
+    pub proximity_detected: bool,
+    /// The over-current flag is set
+    pub over_current: bool,
+    /// The out-of-range status of all electrodes
+    pub out_of_range: OutOfRangeStatus,
+    filtered: ChannelValues<u16>,
+    baselines: ChannelValues<u16>,
+    /// The 10bit filtered data of the ELEPROX electrode
+    pub proximity_filtered: u16,
+    /// The baseline of the ELEPROX electrode, left shifted like the [baseline](Self::baseline) of the channels
+    pub proximity_baseline: u16,
+}
+
+impl Mpr121Snapshot {
+    /// The number of registers covered by a snapshot, from the touch status up to the ELEPROX baseline
+    pub(crate) const LENGTH: usize = (Register::EleproxBaseLine as usize) + 1;
+
+    /// Decodes the snapshot from the registers 0x00 to 0x2A
+    pub(crate) fn from_registers(registers: &[u8; Self::LENGTH]) -> Self {
+        const OVER_CURRENT_PROTECTION_FLAG_MASK: u16 = 0b1 << 15;
+        const ELEPROX_TOUCH_STATUS_MASK: u16 = 0b1 << 12;
+        let read_u16 = |reg: Register| {
+            let index = usize::from(u8::from(reg));
+            u16::from_le_bytes([registers[index], registers[index + 1]])
+        };
+        let read_filtered = |reg: Register| read_u16(reg) & FILTERED_DATA_MASK;
+        let read_baseline = |reg: Register| u16::from(registers[usize::from(u8::from(reg))]) << 2;
+
+        let touch_status = read_u16(Register::TouchStatus0_7);
+        let mut filtered = [0u16; Channel::NUM_CHANNELS as usize];
+        let mut baselines = [0u16; Channel::NUM_CHANNELS as usize];
+        for channel in Channel::iter() {
+            let i = usize::from(u8::from(channel));
+            filtered[i] = read_filtered(Register::get_filtered_data_msb(channel));
+            baselines[i] = read_baseline(Register::get_baseline(channel));
+        }
+
+        Mpr121Snapshot {
+            touched: ChannelSet::from_bits(touch_status),
+            proximity_detected: (touch_status & ELEPROX_TOUCH_STATUS_MASK) > 0,
+            over_current: (touch_status & OVER_CURRENT_PROTECTION_FLAG_MASK) > 0,
+            out_of_range: OutOfRangeStatus::from_registers(read_u16(Register::OORStatus)),
+            filtered: filtered.into(),
+            baselines: baselines.into(),
+            proximity_filtered: read_filtered(Register::EleproxFiltDataMSB),
+            proximity_baseline: read_baseline(Register::EleproxBaseLine),
+        }
+    }
+
+    /// Returns true if the channel is touched
+    pub fn is_touched(&self, channel: Channel) -> bool {
+        self.touched.contains(channel)
+    }
+
+    /// Returns true if the electrode of the channel is out of range
+    pub fn is_out_of_range(&self, channel: Channel) -> bool {
+        self.out_of_range.is_out_of_range(channel)
+    }
+
+    /// Returns the 10bit filtered data of the channel
+    pub fn filtered(&self, channel: Channel) -> u16 {
+        self.filtered[channel]
+    }
+
+    /// Returns the 10bit filtered data of all channels, see `Mpr121::get_all_filtered`
+    pub fn all_filtered(&self) -> ChannelValues<u16> {
+        self.filtered
+    }
+
+    /// Returns the baseline of the channel. The device only exposes the 8 MSB of the 10bit baseline,
+    /// which are left shifted by two bits to be comparable with the filtered data.
+    pub fn baseline(&self, channel: Channel) -> u16 {
+        self.baselines[channel]
+    }
+
+    /// Returns the baselines of all channels, see `Mpr121::get_all_baselines`
+    pub fn all_baselines(&self) -> ChannelValues<u16> {
+        self.baselines
+    }
+
+    /// Returns the difference between the baseline and the filtered data of the channel.
+    /// Touching an electrode lowers the filtered data, so a touch shows up as a positive delta.
+    /// The touch and release thresholds are compared against this value.
+    pub fn delta(&self, channel: Channel) -> i16 {
+        self.baseline(channel) as i16 - self.filtered(channel) as i16
+    }
+}
@@ -1,22 +1,30 @@
 #![no_std]
-use mpr121_hal::mpr121::Mpr121;
+use mpr121_hal::config::{AutoConfig, ElectrodeConfig, Mpr121Config};
+use mpr121_hal::gpio::{GpioMode, GpioPin};
+use mpr121_hal::{
+    AutoConfigStatus, CalibrationLock, Channel, ChannelSet, ChargeCurrent, ChargeTime,
+    DebounceNumber, Electrode, ElectrodeSampleInterval, FirstFilterIterations, Mpr121Error,
+    ProximityMode, SecondFilterIterations,
+};
 
 #[cfg(feature = "sync")]
 mod hal_imports {
     pub use embedded_hal::delay::DelayNs;
     pub use embedded_hal::i2c::I2c;
+    pub use mpr121_hal::mpr121::blocking::Mpr121;
 }
 
 #[cfg(feature = "async")]
 mod hal_imports {
     pub use embedded_hal_async::delay::DelayNs;
     pub use embedded_hal_async::i2c::I2c;
+    pub use mpr121_hal::mpr121::asynch::Mpr121;
 }
 
 use hal_imports::*;
 
 pub fn generic_test_new(i2c: impl I2c, delay: &mut impl DelayNs) {
-    let mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay, true);
+    let mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay);
     assert!(mpr121_sensor.is_ok());
 }
 
@@ -26,7 +34,7 @@ pub fn generic_test_new_default(i2c: impl I2c, delay: &mut impl DelayNs) {
 }
 
 pub fn generic_test_is_over_current_set(i2c: impl I2c, delay: &mut impl DelayNs) {
-    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay, true)
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
         .expect("Sensor Initialisation should not fail");
     let over_current_flag = mpr121_sensor
         .is_over_current_set()
@@ -36,12 +44,506 @@ pub fn generic_test_is_over_current_set(i2c: impl I2c, delay: &mut impl DelayNs)
 }
 
 pub fn generic_test_get_touched(i2c: impl I2c, delay: &mut impl DelayNs) {
-    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay, true)
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
         .expect("Sensor Initialisation should not fail");
-    assert!(
+    assert!(mpr121_sensor
+        .get_touched()
+        .expect("Communication should not fail")
+        .is_empty()); // Nothing should be triggered if not connected to anything
+}
+
+pub fn generic_test_electrode_charge_settings(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail")
+        .stop()
+        .expect("Communication should not fail");
+    let electrode = Electrode::Channel(Channel::Three);
+    let current = ChargeCurrent::new(42);
+    mpr121_sensor
+        .set_electrode_charge_current(electrode, current)
+        .expect("Communication should not fail");
+    mpr121_sensor
+        .set_electrode_charge_time(electrode, Some(ChargeTime::Micros4))
+        .expect("Communication should not fail");
+
+    assert_eq!(
+        mpr121_sensor
+            .get_electrode_charge_current(electrode)
+            .expect("Communication should not fail"),
+        current
+    );
+    assert_eq!(
+        mpr121_sensor
+            .get_electrode_charge_time(electrode)
+            .expect("Communication should not fail"),
+        Some(ChargeTime::Micros4)
+    );
+    // The neighbouring electrode shares the charge time register and must be left untouched
+    assert_eq!(
+        mpr121_sensor
+            .get_electrode_charge_time(Electrode::Channel(Channel::Two))
+            .expect("Communication should not fail"),
+        None
+    );
+}
+
+pub fn generic_test_proximity_mode(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail");
+    assert_eq!(mpr121_sensor.get_proximity_mode(), ProximityMode::Disabled);
+    let electrode_config = mpr121_sensor.electrode_config();
+    let mut mpr121_sensor = mpr121_sensor.stop().expect("Communication should not fail");
+    mpr121_sensor
+        .set_proximity_thresholds(4, 2)
+        .expect("Communication should not fail");
+    let mut mpr121_sensor = mpr121_sensor
+        .start(electrode_config)
+        .expect("Communication should not fail");
+    mpr121_sensor
+        .set_proximity_mode(ProximityMode::Electrodes0To3)
+        .expect("Communication should not fail");
+    assert_eq!(
+        mpr121_sensor.get_proximity_mode(),
+        ProximityMode::Electrodes0To3
+    );
+    // The cached state has to match the device
+    mpr121_sensor
+        .resync()
+        .expect("Communication should not fail");
+    assert_eq!(
+        mpr121_sensor.get_proximity_mode(),
+        ProximityMode::Electrodes0To3
+    );
+    assert!(!mpr121_sensor
+        .is_proximity_detected()
+        .expect("Communication should not fail"));
+
+    // Disabling the proximity detection without touch electrodes would stop the device
+    mpr121_sensor
+        .set_proximity_mode(ProximityMode::Disabled)
+        .expect("Communication should not fail");
+    let electrode_config = ElectrodeConfig {
+        electrode_count: 0,
+        proximity_mode: ProximityMode::Electrodes0To11,
+        ..mpr121_sensor.electrode_config()
+    };
+    let mut mpr121_sensor = mpr121_sensor
+        .stop()
+        .expect("Communication should not fail")
+        .start(electrode_config)
+        .expect("Communication should not fail");
+    assert!(matches!(
+        mpr121_sensor.set_proximity_mode(ProximityMode::Disabled),
+        Err(Mpr121Error::NothingEnabled)
+    ));
+    assert_eq!(
+        mpr121_sensor.get_proximity_mode(),
+        ProximityMode::Electrodes0To11
+    );
+}
+
+pub fn generic_test_stop_start(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail");
+    assert!(mpr121_sensor.is_running());
+    let electrode_config = ElectrodeConfig {
+        electrode_count: 6,
+        ..mpr121_sensor.electrode_config()
+    };
+    let previous_config = mpr121_sensor.electrode_config();
+    let mut mpr121_sensor = mpr121_sensor.stop().expect("Communication should not fail");
+    assert!(!mpr121_sensor.is_running());
+    assert_eq!(mpr121_sensor.previous_electrode_config(), previous_config);
+    // The calibration lock survives the STOP mode
+    assert_eq!(
+        mpr121_sensor.get_calibration_lock(),
+        previous_config.calibration_lock
+    );
+    mpr121_sensor
+        .set_thresholds(20, 10)
+        .expect("Communication should not fail");
+    let mut mpr121_sensor = mpr121_sensor
+        .start(electrode_config)
+        .expect("Communication should not fail");
+    assert!(mpr121_sensor.is_running());
+    // The cached state has to match the device
+    mpr121_sensor
+        .resync()
+        .expect("Communication should not fail");
+    assert_eq!(mpr121_sensor.electrode_config(), electrode_config);
+
+    let mut mpr121_sensor = mpr121_sensor
+        .stop()
+        .expect("Communication should not fail")
+        .resume()
+        .expect("Communication should not fail");
+    mpr121_sensor
+        .resync()
+        .expect("Communication should not fail");
+    assert_eq!(mpr121_sensor.electrode_config(), electrode_config);
+
+    let mpr121_sensor = mpr121_sensor.stop().expect("Communication should not fail");
+    assert!(matches!(
+        mpr121_sensor.start(ElectrodeConfig {
+            electrode_count: 0,
+            proximity_mode: ProximityMode::Disabled,
+            ..electrode_config
+        }),
+        Err(Mpr121Error::NothingEnabled)
+    ));
+}
+
+pub fn generic_test_gpio_refuses_touch_electrode(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail");
+    // All 12 electrodes are enabled for touch sensing by default
+    assert!(matches!(
+        mpr121_sensor.configure_gpio(GpioPin::Eleven, GpioMode::Output),
+        Err(Mpr121Error::PinInUse(GpioPin::Eleven))
+    ));
+}
+
+pub fn generic_test_gpio_output(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let config = Mpr121Config::default().with_electrode_count(4);
+    let mut mpr121_sensor =
+        Mpr121::with_config(i2c, mpr121_hal::Mpr121Address::Default, delay, config)
+            .expect("Sensor Initialisation should not fail");
+    // ELE6 and ELE7 share the PWM1 register
+    for pin in [GpioPin::Six, GpioPin::Seven] {
+        mpr121_sensor
+            .configure_gpio(pin, GpioMode::Output)
+            .expect("Communication should not fail");
+    }
+    mpr121_sensor
+        .set_gpio_pwm(GpioPin::Six, 5)
+        .expect("Communication should not fail");
+    mpr121_sensor
+        .set_gpio_pwm(GpioPin::Seven, 200)
+        .expect("Communication should not fail");
+    assert_eq!(
+        mpr121_sensor
+            .get_gpio_pwm(GpioPin::Six)
+            .expect("Communication should not fail"),
+        5
+    );
+    // Duty cycles above the maximum of 15 are clamped
+    assert_eq!(
+        mpr121_sensor
+            .get_gpio_pwm(GpioPin::Seven)
+            .expect("Communication should not fail"),
+        15
+    );
+    assert!(mpr121_sensor
+        .is_gpio_high(GpioPin::Six)
+        .expect("Communication should not fail"));
+
+    // A duty of 0 turns the pin off without touching its neighbour
+    mpr121_sensor
+        .set_gpio_pwm(GpioPin::Six, 0)
+        .expect("Communication should not fail");
+    assert_eq!(
+        mpr121_sensor
+            .get_gpio_pwm(GpioPin::Six)
+            .expect("Communication should not fail"),
+        0
+    );
+    assert!(!mpr121_sensor
+        .is_gpio_high(GpioPin::Six)
+        .expect("Communication should not fail"));
+    assert!(mpr121_sensor
+        .is_gpio_high(GpioPin::Seven)
+        .expect("Communication should not fail"));
+
+    mpr121_sensor
+        .set_gpio_high(GpioPin::Six)
+        .expect("Communication should not fail");
+    assert!(mpr121_sensor
+        .is_gpio_high(GpioPin::Six)
+        .expect("Communication should not fail"));
+    mpr121_sensor
+        .toggle_gpio(GpioPin::Seven)
+        .expect("Communication should not fail");
+    assert!(!mpr121_sensor
+        .is_gpio_high(GpioPin::Seven)
+        .expect("Communication should not fail"));
+    mpr121_sensor
+        .set_gpio_low(GpioPin::Six)
+        .expect("Communication should not fail");
+    assert!(!mpr121_sensor
+        .is_gpio_high(GpioPin::Six)
+        .expect("Communication should not fail"));
+}
+
+pub fn generic_test_with_config(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let config = Mpr121Config::default()
+        .with_thresholds(20, 10)
+        .with_debounce(DebounceNumber::Two, DebounceNumber::One)
+        .with_electrode_count(4);
+    let mut mpr121_sensor =
+        Mpr121::with_config(i2c, mpr121_hal::Mpr121Address::Default, delay, config)
+            .expect("Sensor Initialisation should not fail");
+    // Electrodes beyond the electrode count are free to be used as GPIO
+    assert!(mpr121_sensor
+        .configure_gpio(GpioPin::Four, GpioMode::Output)
+        .is_ok());
+}
+
+pub fn generic_test_auto_config_disabled(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let config = Mpr121Config::default().with_auto_config(AutoConfig::disabled());
+    let mut mpr121_sensor =
+        Mpr121::with_config(i2c, mpr121_hal::Mpr121Address::Default, delay, config)
+            .expect("Sensor Initialisation should not fail");
+    // Without auto configuration there is no configuration that could have failed
+    assert_eq!(
+        mpr121_sensor
+            .get_auto_config_status()
+            .expect("Communication should not fail"),
+        AutoConfigStatus::default()
+    );
+}
+
+pub fn generic_test_get_snapshot(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail");
+    let snapshot = mpr121_sensor
+        .get_snapshot()
+        .expect("Communication should not fail");
+    // Nothing should be triggered if not connected to anything
+    assert!(snapshot.touched.is_empty());
+    assert!(!snapshot.over_current);
+}
+
+pub fn generic_test_poll_events(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail");
+    // Nothing should be triggered if not connected to anything
+    assert_eq!(
+        mpr121_sensor
+            .poll_events()
+            .expect("Communication should not fail")
+            .len(),
+        0
+    );
+}
+
+pub fn generic_test_channel_thresholds(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail")
+        .stop()
+        .expect("Communication should not fail");
+    let mut thresholds = [(0u8, 0u8); Channel::NUM_CHANNELS as usize];
+    for (i, threshold) in thresholds.iter_mut().enumerate() {
+        *threshold = (10 + i as u8, 5 + i as u8);
+    }
+    mpr121_sensor
+        .set_all_channel_thresholds(thresholds)
+        .expect("Communication should not fail");
+    mpr121_sensor
+        .set_channel_thresholds(Channel::Nine, 40, 20)
+        .expect("Communication should not fail");
+    thresholds[usize::from(u8::from(Channel::Nine))] = (40, 20);
+
+    assert_eq!(
+        mpr121_sensor
+            .get_all_channel_thresholds()
+            .expect("Communication should not fail"),
+        thresholds
+    );
+    assert_eq!(
+        mpr121_sensor
+            .get_channel_thresholds(Channel::Nine)
+            .expect("Communication should not fail"),
+        (40, 20)
+    );
+}
+
+pub fn generic_test_read_config(i2c: impl I2c, delay: &mut impl DelayNs) {
+    // The auto-configuration would override the charge settings of the electrodes
+    let config = Mpr121Config::default()
+        .with_thresholds(20, 10)
+        .with_channel_thresholds(Channel::Five, 30, 15)
+        .with_proximity_thresholds(8, 4)
+        .with_debounce(DebounceNumber::Two, DebounceNumber::One)
+        .with_charge(
+            ChargeCurrent::new(24).expect("24µA is a valid charge current"),
+            ChargeTime::Micros1,
+        )
+        .with_electrode_charge(
+            Electrode::Channel(Channel::Three),
+            ChargeCurrent::new(42),
+            Some(ChargeTime::Micros2),
+        )
+        .with_filter_iterations(
+            FirstFilterIterations::Samples10,
+            SecondFilterIterations::Samples6,
+        )
+        .with_sample_interval(ElectrodeSampleInterval::Millis8)
+        .with_auto_config(AutoConfig::disabled())
+        .with_electrode_count(6)
+        .with_proximity_mode(ProximityMode::Electrodes0To1);
+    // Replacing the auto-configuration keeps the configured first filter iterations
+    assert_eq!(
+        config.filter_iterations(),
+        (
+            FirstFilterIterations::Samples10,
+            SecondFilterIterations::Samples6
+        )
+    );
+    let mut mpr121_sensor =
+        Mpr121::with_config(i2c, mpr121_hal::Mpr121Address::Default, delay, config)
+            .expect("Sensor Initialisation should not fail");
+    let read_config = mpr121_sensor
+        .read_config()
+        .expect("Communication should not fail");
+    assert_eq!(read_config, config);
+    assert_eq!(
+        read_config.filter_iterations().0,
+        FirstFilterIterations::Samples10
+    );
+}
+
+pub fn generic_test_dump_registers(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail");
+    let dump = mpr121_sensor
+        .dump_registers()
+        .expect("Communication should not fail");
+    // The default configuration enables all 12 electrodes with baseline tracking loaded from the 5 MSB
+    assert_eq!(dump.get(0x5E), Some(0x8C));
+    assert_eq!(dump.get(0x41), Some(Mpr121Config::DEFAULT_TOUCH_THRESHOLD));
+    assert_eq!(dump.get(0x80), None);
+}
+
+pub fn generic_test_electrode_count(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let config = Mpr121Config::default().with_electrode_count(4);
+    let mut mpr121_sensor =
+        Mpr121::with_config(i2c, mpr121_hal::Mpr121Address::Default, delay, config)
+            .expect("Sensor Initialisation should not fail");
+    assert_eq!(mpr121_sensor.enabled_electrodes(), ChannelSet::first(4));
+    assert!(mpr121_sensor.get_filtered(Channel::Three).is_ok());
+    assert!(matches!(
+        mpr121_sensor.get_filtered(Channel::Five),
+        Err(Mpr121Error::ChannelDisabled(Channel::Five))
+    ));
+
+    mpr121_sensor
+        .set_electrode_count(8)
+        .expect("Communication should not fail");
+    assert_eq!(mpr121_sensor.enabled_electrodes(), ChannelSet::first(8));
+    assert!(mpr121_sensor.get_baseline(Channel::Five).is_ok());
+    assert!(matches!(
+        mpr121_sensor.get_sensor_touch(Channel::Eight),
+        Err(Mpr121Error::ChannelDisabled(Channel::Eight))
+    ));
+    // The spare electrodes remain available as GPIO
+    assert!(mpr121_sensor
+        .configure_gpio(GpioPin::Eight, GpioMode::Output)
+        .is_ok());
+    assert!(matches!(
+        mpr121_sensor.configure_gpio(GpioPin::Seven, GpioMode::Output),
+        Err(Mpr121Error::PinInUse(GpioPin::Seven))
+    ));
+
+    // Without proximity detection no electrode would be left in RUN mode
+    assert!(matches!(
+        mpr121_sensor.set_electrode_count(0),
+        Err(Mpr121Error::NothingEnabled)
+    ));
+    assert!(mpr121_sensor.is_running());
+    assert_eq!(mpr121_sensor.enabled_electrodes(), ChannelSet::first(8));
+    mpr121_sensor
+        .set_proximity_mode(ProximityMode::Electrodes0To3)
+        .expect("Communication should not fail");
+    mpr121_sensor
+        .set_electrode_count(0)
+        .expect("Communication should not fail");
+    assert!(mpr121_sensor.is_running());
+    assert!(mpr121_sensor.enabled_electrodes().is_empty());
+}
+
+pub fn generic_test_calibration_lock(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail");
+    assert_eq!(
+        mpr121_sensor.get_calibration_lock(),
+        CalibrationLock::TrackingInit5Bit
+    );
+    for calibration_lock in [
+        CalibrationLock::TrackingDisabled,
+        CalibrationLock::TrackingFromCurrent,
+    ] {
+        mpr121_sensor
+            .set_calibration_lock(calibration_lock)
+            .expect("Communication should not fail");
+        assert_eq!(mpr121_sensor.get_calibration_lock(), calibration_lock);
+        assert_eq!(
+            mpr121_sensor
+                .read_config()
+                .expect("Communication should not fail")
+                .calibration_lock(),
+            calibration_lock
+        );
+        // The electrodes stay enabled
+        assert_eq!(
+            mpr121_sensor.enabled_electrodes(),
+            ChannelSet::first(Channel::NUM_CHANNELS)
+        );
+    }
+}
+
+pub fn generic_test_analog_front_end(i2c: impl I2c, delay: &mut impl DelayNs) {
+    let mut mpr121_sensor = Mpr121::new(i2c, mpr121_hal::Mpr121Address::Default, delay)
+        .expect("Sensor Initialisation should not fail")
+        .stop()
+        .expect("Communication should not fail");
+    let current = ChargeCurrent::new(32).expect("32µA is a valid charge current");
+    mpr121_sensor
+        .set_filter_iterations(
+            FirstFilterIterations::Samples18,
+            SecondFilterIterations::Samples10,
+        )
+        .expect("Communication should not fail");
+    mpr121_sensor
+        .set_sample_interval(ElectrodeSampleInterval::Millis16)
+        .expect("Communication should not fail");
+    mpr121_sensor
+        .set_global_charge(current, ChargeTime::Micros2)
+        .expect("Communication should not fail");
+    // The auto-configuration keeps the first filter iterations of AFE1
+    mpr121_sensor
+        .set_auto_config(&AutoConfig {
+            first_filter_iterations: FirstFilterIterations::Samples10,
+            ..AutoConfig::disabled()
+        })
+        .expect("Communication should not fail");
+
+    assert_eq!(
+        mpr121_sensor
+            .get_filter_iterations()
+            .expect("Communication should not fail"),
+        (
+            FirstFilterIterations::Samples18,
+            SecondFilterIterations::Samples10
+        )
+    );
+    assert_eq!(
+        mpr121_sensor
+            .get_sample_interval()
+            .expect("Communication should not fail"),
+        ElectrodeSampleInterval::Millis16
+    );
+    assert_eq!(
+        mpr121_sensor
+            .get_global_charge()
+            .expect("Communication should not fail"),
+        (current, ChargeTime::Micros2)
+    );
+    assert_eq!(
         mpr121_sensor
-            .get_touched()
+            .read_config()
             .expect("Communication should not fail")
-            == 0
-    ); // Nothing should be triggered if not connected to anything
+            .auto_config()
+            .first_filter_iterations,
+        FirstFilterIterations::Samples18
+    );
 }
@@ -25,3 +25,93 @@ pub fn test_get_touched() {
     let mut delay = i2c_driver::setup_delay();
     generic_test_get_touched(i2c_bus, &mut delay);
 }
+#[test]
+pub fn test_electrode_charge_settings() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_electrode_charge_settings(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_proximity_mode() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_proximity_mode(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_stop_start() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_stop_start(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_gpio_refuses_touch_electrode() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_gpio_refuses_touch_electrode(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_gpio_output() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_gpio_output(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_with_config() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_with_config(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_auto_config_disabled() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_auto_config_disabled(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_get_snapshot() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_get_snapshot(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_poll_events() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_poll_events(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_channel_thresholds() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_channel_thresholds(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_read_config() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_read_config(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_dump_registers() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_dump_registers(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_electrode_count() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_electrode_count(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_calibration_lock() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_calibration_lock(i2c_bus, &mut delay);
+}
+#[test]
+pub fn test_analog_front_end() {
+    let i2c_bus = i2c_driver::setup_i2c().expect("I2C Bus failed to acquire");
+    let mut delay = i2c_driver::setup_delay();
+    generic_test_analog_front_end(i2c_bus, &mut delay);
+}
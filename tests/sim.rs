@@ -0,0 +1,754 @@
+//! Runs the common tests against the simulated device, so they do not need any hardware
+use core::cell::{Cell, RefCell};
+use core::error::Error;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{OutputPin, StatefulOutputPin};
+use embedded_hal::i2c::{self, I2c, Operation};
+use embedded_hal::pwm::SetDutyCycle;
+use mpr121_hal::config::Mpr121Config;
+use mpr121_hal::events::TouchEvent;
+use mpr121_hal::gpio::{GpioMode, GpioPin, Mpr121Pin};
+use mpr121_hal::mpr121::blocking::Mpr121;
+use mpr121_hal::sim::Mpr121Sim;
+use mpr121_hal::{
+    Channel, ChannelSet, Electrode, FirstFilterIterations, Mpr121Address, Mpr121Error,
+    OutOfRangeStatus, ProximityMode,
+};
+use tests_common::*;
+
+/// The simulated device responds immediately, so there is nothing to wait for
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// A fault injected by the [FaultyBus]
+#[derive(Clone, Copy)]
+enum Fault {
+    /// Every transaction fails with a bus error
+    Fail,
+    /// Every read returns 0xFF instead of the register values
+    Corrupt,
+}
+
+/// A bus in front of the simulated device, which fails or corrupts the transactions on demand
+struct FaultyBus<'a> {
+    sim: &'a Mpr121Sim,
+    fault: Cell<Option<Fault>>,
+}
+
+impl i2c::ErrorType for &FaultyBus<'_> {
+    type Error = i2c::ErrorKind;
+}
+
+impl I2c for &FaultyBus<'_> {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut sim = self.sim;
+        match self.fault.get() {
+            Some(Fault::Fail) => Err(i2c::ErrorKind::Bus),
+            Some(Fault::Corrupt) => {
+                sim.transaction(address, operations)?;
+                for operation in operations {
+                    if let Operation::Read(buffer) = operation {
+                        buffer.fill(0xFF);
+                    }
+                }
+                Ok(())
+            }
+            None => sim.transaction(address, operations),
+        }
+    }
+}
+
+#[test]
+fn test_new() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_new(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_new_default() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_new_default(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_is_over_current_set() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_is_over_current_set(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_get_touched() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_get_touched(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_electrode_charge_settings() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_electrode_charge_settings(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_proximity_mode() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_proximity_mode(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_stop_start() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_stop_start(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_gpio_refuses_touch_electrode() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_gpio_refuses_touch_electrode(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_with_config() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_with_config(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_auto_config_disabled() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_auto_config_disabled(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_get_snapshot() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_get_snapshot(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_poll_events() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_poll_events(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_channel_thresholds() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_channel_thresholds(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_read_config() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_read_config(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_dump_registers() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_dump_registers(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_electrode_count() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_electrode_count(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+    // ELEPROX_EN=0b10, ELE_EN=0
+    assert_eq!(sim.register(0x5E) & 0x3F, 0b10_0000);
+}
+
+#[test]
+fn test_calibration_lock() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_calibration_lock(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_analog_front_end() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_analog_front_end(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+    // FFI=18, CDC=32µA in AFE1, CDT=2µs, SFI=10, ESI=16ms in AFE2
+    assert_eq!(sim.register(0x5C), 0b1010_0000);
+    assert_eq!(sim.register(0x5D), 0b0111_0100);
+    // The first filter iterations of the auto-configuration follow AFE1
+    assert_eq!(sim.register(0x7B) >> 6, 0b10);
+}
+
+#[test]
+fn test_read_config_reports_mismatched_filter_iterations() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    // FFI=34 in AutoConfig0, while AFE1 keeps FFI=6
+    sim.set_register(0x7B, sim.register(0x7B) | 0b1100_0000);
+    let config = mpr121_sensor
+        .read_config()
+        .expect("Communication should not fail");
+    assert_eq!(
+        config.filter_iterations().0,
+        FirstFilterIterations::Samples6
+    );
+    assert_eq!(
+        config.auto_config().first_filter_iterations,
+        FirstFilterIterations::Samples34
+    );
+}
+
+#[test]
+fn test_dump_display() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    sim.set_touched(Electrode::Channel(Channel::Two), true);
+    sim.set_filtered(Electrode::Channel(Channel::Two), 500);
+    let dump = mpr121_sensor
+        .dump_registers()
+        .expect("Communication should not fail");
+    let text = dump.to_string();
+    assert!(text.contains("0x00 TOUCH STATUS 0x0004: ELE={Two} EPROX=0 OVCF=0"));
+    assert!(text.contains("0x08 ELE2 FILTERED 500"));
+    assert!(text.contains("0x5e ECR 0x8c: CL=2 ELEPROX_EN=0 ELE_EN=12"));
+    assert!(text.contains("0x5c AFE1 0x10: FFI=0 CDC=16uA"));
+}
+
+#[test]
+fn test_register_pointer_wraps() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    sim.set_register(0x00, 0x11);
+    sim.set_register(0x2A, 0x22);
+    sim.set_register(0x2B, 0x33);
+    sim.set_register(0x7F, 0x44);
+    let address = Mpr121Address::Default.into();
+    let mut buffer = [0u8; 2];
+    I2c::write_read(&mut &sim, address, &[0x2A], &mut buffer)
+        .expect("Communication should not fail");
+    assert_eq!(buffer, [0x22, 0x11]);
+    I2c::write_read(&mut &sim, address, &[0x7F], &mut buffer)
+        .expect("Communication should not fail");
+    assert_eq!(buffer, [0x44, 0x33]);
+    // Writes wrap within the configuration registers as well
+    I2c::write(&mut &sim, address, &[0x7F, 0x55, 0x66]).expect("Communication should not fail");
+    assert_eq!(sim.register(0x7F), 0x55);
+    assert_eq!(sim.register(0x2B), 0x66);
+    assert_eq!(sim.register(0x80), 0);
+}
+
+#[test]
+fn test_gpio_output() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    generic_test_gpio_output(&sim, &mut NoDelay);
+    assert_eq!(sim.rejected_writes(), 0);
+    // ELE6 uses the lower, ELE7 the upper nibble of PWM1
+    assert_eq!(sim.register(0x82), 0xF0);
+    // ELE7 was toggled low, ELE6 driven low
+    assert_eq!(sim.register(0x75), 0);
+}
+
+#[test]
+fn test_gpio_pin() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let config = Mpr121Config::default().with_electrode_count(4);
+    let driver = RefCell::new(
+        Mpr121::with_config(&sim, Mpr121Address::Default, &mut NoDelay, config)
+            .expect("Sensor Initialisation should not fail"),
+    );
+    let mut pin_ten = Mpr121Pin::new(&driver, GpioPin::Ten, GpioMode::OutputHighSide)
+        .expect("Communication should not fail");
+    let mut pin_eleven = Mpr121Pin::new(&driver, GpioPin::Eleven, GpioMode::Output)
+        .expect("Communication should not fail");
+    assert_eq!(pin_ten.max_duty_cycle(), 15);
+
+    pin_ten
+        .set_duty_cycle(9)
+        .expect("Communication should not fail");
+    pin_eleven
+        .set_duty_cycle_fully_on()
+        .expect("Communication should not fail");
+    // ELE10 uses the lower, ELE11 the upper nibble of PWM3, the other PWM registers stay untouched
+    assert_eq!(sim.register(0x84), 0xF9);
+    for address in 0x81..=0x83 {
+        assert_eq!(sim.register(address), 0);
+    }
+    // Both pins are driven high, DAT bits 6 and 7
+    assert_eq!(sim.register(0x75), 0b1100_0000);
+    assert!(pin_ten
+        .is_set_high()
+        .expect("Communication should not fail"));
+
+    pin_ten.set_low().expect("Communication should not fail");
+    assert!(pin_ten.is_set_low().expect("Communication should not fail"));
+    assert_eq!(sim.register(0x75), 0b1000_0000);
+    pin_eleven.toggle().expect("Communication should not fail");
+    assert!(pin_eleven
+        .is_set_low()
+        .expect("Communication should not fail"));
+    pin_ten.set_high().expect("Communication should not fail");
+    assert_eq!(sim.register(0x75), 0b0100_0000);
+
+    // A duty of 0 clears only the nibble of the pin and drives it low
+    pin_ten
+        .set_duty_cycle_fully_off()
+        .expect("Communication should not fail");
+    assert_eq!(sim.register(0x84), 0xF0);
+    assert_eq!(sim.register(0x75), 0);
+    assert_eq!(
+        driver
+            .borrow_mut()
+            .get_gpio_pwm(GpioPin::Eleven)
+            .expect("Communication should not fail"),
+        15
+    );
+    assert_eq!(sim.rejected_writes(), 0);
+}
+
+#[test]
+fn test_touch_status() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    sim.set_touched(Electrode::Channel(Channel::Three), true);
+    sim.set_touched(Electrode::Channel(Channel::Eleven), true);
+    assert_eq!(
+        mpr121_sensor
+            .get_touched()
+            .expect("Communication should not fail"),
+        ChannelSet::from_iter([Channel::Three, Channel::Eleven])
+    );
+    sim.set_touched(Electrode::Channel(Channel::Three), false);
+    assert!(!mpr121_sensor
+        .get_sensor_touch(Channel::Three)
+        .expect("Communication should not fail"));
+}
+
+#[test]
+fn test_delta_ignores_reserved_bits() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    sim.set_filtered(Electrode::Channel(Channel::Two), 600);
+    sim.set_baseline(Electrode::Channel(Channel::Two), 640);
+    // Set the reserved upper bits of the filtered data MSB of ELE2
+    sim.set_register(0x09, sim.register(0x09) | 0b1111_1100);
+    assert_eq!(
+        mpr121_sensor
+            .get_delta(Channel::Two)
+            .expect("Communication should not fail"),
+        40
+    );
+    let snapshot = mpr121_sensor
+        .get_snapshot()
+        .expect("Communication should not fail");
+    assert_eq!(snapshot.filtered(Channel::Two), 600);
+    assert_eq!(snapshot.delta(Channel::Two), 40);
+}
+
+#[test]
+fn test_all_channel_data() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    for channel in Channel::iter() {
+        let index = u16::from(u8::from(channel));
+        sim.set_filtered(Electrode::Channel(channel), 500 + index);
+        // The baselines hold the 8 MSB only
+        sim.set_baseline(Electrode::Channel(channel), 600 + 4 * index);
+    }
+    let filtered = mpr121_sensor
+        .get_all_filtered()
+        .expect("Communication should not fail");
+    let baselines = mpr121_sensor
+        .get_all_baselines()
+        .expect("Communication should not fail");
+    for channel in Channel::iter() {
+        let index = u16::from(u8::from(channel));
+        assert_eq!(filtered[channel], 500 + index);
+        assert_eq!(baselines[channel], 600 + 4 * index);
+    }
+    assert_eq!(filtered.iter().nth(7), Some((Channel::Seven, &507)));
+    assert_eq!(baselines.into_array()[0], 600);
+}
+
+#[test]
+fn test_out_of_range() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    assert_eq!(
+        mpr121_sensor
+            .out_of_range()
+            .expect("Communication should not fail"),
+        OutOfRangeStatus::default()
+    );
+
+    // ELE0, ELE2 and ELE7 in 0x02, ELE9, ELE11, ELEPROX and ACFF in 0x03
+    sim.set_register(0x02, 0b1000_0101);
+    sim.set_register(0x03, 0b1001_1010);
+    let status = mpr121_sensor
+        .out_of_range()
+        .expect("Communication should not fail");
+    assert_eq!(
+        status.electrodes,
+        ChannelSet::from_iter([
+            Channel::Zero,
+            Channel::Two,
+            Channel::Seven,
+            Channel::Nine,
+            Channel::Eleven
+        ])
+    );
+    assert!(status.is_out_of_range(Channel::Nine));
+    assert!(!status.is_out_of_range(Channel::Eight));
+    assert!(status.eleprox);
+    assert!(status.auto_config.auto_config_failed);
+    assert!(!status.auto_config.auto_reconfig_failed);
+
+    // Only ARFF
+    sim.set_register(0x02, 0);
+    sim.set_register(0x03, 0b0100_0000);
+    let status = mpr121_sensor
+        .out_of_range()
+        .expect("Communication should not fail");
+    assert!(status.electrodes.is_empty());
+    assert!(!status.eleprox);
+    assert!(!status.auto_config.auto_config_failed);
+    assert!(status.auto_config.auto_reconfig_failed);
+    assert_eq!(
+        mpr121_sensor
+            .get_auto_config_status()
+            .expect("Communication should not fail"),
+        status.auto_config
+    );
+}
+
+#[test]
+fn test_snapshot_data() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    sim.set_filtered(Electrode::Channel(Channel::Five), 600);
+    sim.set_baseline(Electrode::Channel(Channel::Five), 640);
+    sim.set_out_of_range(Electrode::Channel(Channel::Two), true);
+    let snapshot = mpr121_sensor
+        .get_snapshot()
+        .expect("Communication should not fail");
+    assert_eq!(snapshot.filtered(Channel::Five), 600);
+    assert_eq!(snapshot.baseline(Channel::Five), 640);
+    // The snapshot matches the bulk reads of the driver
+    assert_eq!(
+        snapshot.all_filtered(),
+        mpr121_sensor
+            .get_all_filtered()
+            .expect("Communication should not fail")
+    );
+    assert_eq!(
+        snapshot.all_baselines(),
+        mpr121_sensor
+            .get_all_baselines()
+            .expect("Communication should not fail")
+    );
+    assert_eq!(snapshot.delta(Channel::Five), 40);
+    assert!(snapshot.is_out_of_range(Channel::Two));
+}
+
+#[test]
+fn test_baseline_and_delta() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    sim.set_filtered(Electrode::Channel(Channel::Zero), 700);
+    sim.set_baseline(Electrode::Channel(Channel::Zero), 720);
+    sim.set_filtered(Electrode::Channel(Channel::Eleven), 600);
+    sim.set_baseline(Electrode::Channel(Channel::Eleven), 580);
+    sim.set_baseline(Electrode::Eleprox, 512);
+    assert_eq!(
+        mpr121_sensor
+            .get_baseline(Channel::Zero)
+            .expect("Communication should not fail"),
+        720
+    );
+    assert_eq!(
+        mpr121_sensor
+            .get_all_baselines()
+            .expect("Communication should not fail")[Channel::Eleven],
+        580
+    );
+    assert_eq!(
+        mpr121_sensor
+            .get_proximity_baseline()
+            .expect("Communication should not fail"),
+        512
+    );
+    assert_eq!(
+        mpr121_sensor
+            .get_delta(Channel::Zero)
+            .expect("Communication should not fail"),
+        20
+    );
+    assert_eq!(
+        mpr121_sensor
+            .get_delta(Channel::Eleven)
+            .expect("Communication should not fail"),
+        -20
+    );
+}
+
+#[test]
+fn test_over_current_fails_initialisation() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    sim.set_over_current(true);
+    assert!(matches!(
+        Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay),
+        Err(mpr121_hal::Mpr121Error::OverCurrent)
+    ));
+}
+
+#[test]
+fn test_wrong_address_is_not_acknowledged() {
+    let sim = Mpr121Sim::new(Mpr121Address::Vdd);
+    let error = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .err()
+        .expect("Initialisation at the wrong address should fail");
+    assert_eq!(
+        error.bus_error().map(embedded_hal::i2c::Error::kind),
+        Some(embedded_hal::i2c::ErrorKind::NoAcknowledge(
+            embedded_hal::i2c::NoAcknowledgeSource::Address
+        ))
+    );
+}
+
+#[test]
+fn test_error_display() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let bus = FaultyBus {
+        sim: &sim,
+        fault: Cell::new(Some(Fault::Corrupt)),
+    };
+    let error = Mpr121::new(&bus, Mpr121Address::Default, &mut NoDelay)
+        .err()
+        .expect("Initialisation with corrupted reads should fail");
+    assert_eq!(
+        error.to_string(),
+        "unexpected device, register GlobalChargeDischargeCurrentConfig is 0xff instead of 0x10"
+    );
+    assert_eq!(error.bus_error(), None);
+
+    bus.fault.set(Some(Fault::Fail));
+    let error = Mpr121::new(&bus, Mpr121Address::Default, &mut NoDelay)
+        .err()
+        .expect("Initialisation on a failing bus should fail");
+    assert_eq!(
+        error.to_string(),
+        "soft reset failed to write register SoftReset: Bus"
+    );
+    assert_eq!(error.bus_error(), Some(&i2c::ErrorKind::Bus));
+
+    bus.fault.set(None);
+    let mut mpr121_sensor = Mpr121::new(&bus, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    bus.fault.set(Some(Fault::Fail));
+    let error = mpr121_sensor
+        .get_touched()
+        .expect_err("Reading from a failing bus should fail");
+    assert!(matches!(
+        error,
+        Mpr121Error::ReadError(_, i2c::ErrorKind::Bus)
+    ));
+    assert_eq!(
+        error.to_string(),
+        "failed to read register TouchStatus0_7: Bus"
+    );
+    assert_eq!(error.bus_error(), Some(&i2c::ErrorKind::Bus));
+    // The bus error is only required to implement Debug, so it is not chained as the source
+    assert!(error.source().is_none());
+    let error = mpr121_sensor
+        .set_proximity_mode(ProximityMode::Electrodes0To1)
+        .expect_err("Writing to a failing bus should fail");
+    assert!(matches!(
+        error,
+        Mpr121Error::WriteError(_, i2c::ErrorKind::Bus)
+    ));
+    assert_eq!(error.to_string(), "failed to write register Ecr: Bus");
+    assert_eq!(error.bus_error(), Some(&i2c::ErrorKind::Bus));
+    assert!(error.source().is_none());
+
+    for (error, text) in [
+        (
+            Mpr121Error::ChannelExceed,
+            "channel exceeds the number of channels",
+        ),
+        (
+            Mpr121Error::OverCurrent,
+            "over-current detected on the REXT pin",
+        ),
+        (
+            Mpr121Error::PinInUse(GpioPin::Four),
+            "Four is in use as touch or proximity electrode",
+        ),
+        (
+            Mpr121Error::IrqPinError(embedded_hal::digital::ErrorKind::Other),
+            "failed to wait for the IRQ pin: Other",
+        ),
+        (
+            Mpr121Error::ChannelDisabled(Channel::Five),
+            "Five is not enabled for touch sensing",
+        ),
+        (
+            Mpr121Error::NothingEnabled,
+            "neither touch nor proximity electrodes are enabled",
+        ),
+    ] {
+        assert_eq!(error.to_string(), text);
+        assert_eq!(error.bus_error(), None::<&i2c::ErrorKind>);
+        assert!(error.source().is_none());
+    }
+}
+
+#[test]
+fn test_touch_events() {
+    let sim = Mpr121Sim::new(Mpr121Address::Default);
+    let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+        .expect("Sensor Initialisation should not fail");
+    sim.set_touched(Electrode::Channel(Channel::Seven), true);
+    sim.set_touched(Electrode::Channel(Channel::One), true);
+    sim.set_touched(Electrode::Eleprox, true);
+    let mut events = mpr121_sensor
+        .poll_events()
+        .expect("Communication should not fail");
+    assert_eq!(events.next(), Some(TouchEvent::Touched(Channel::One)));
+    assert_eq!(events.next(), Some(TouchEvent::Touched(Channel::Seven)));
+    assert_eq!(events.next(), Some(TouchEvent::ProximityDetected));
+    assert_eq!(events.next(), None);
+
+    // Unchanged electrodes are not reported again
+    sim.set_touched(Electrode::Channel(Channel::One), false);
+    sim.set_over_current(true);
+    let mut events = mpr121_sensor
+        .poll_events()
+        .expect("Communication should not fail");
+    assert_eq!(events.next(), Some(TouchEvent::Released(Channel::One)));
+    assert_eq!(events.next(), Some(TouchEvent::OverCurrentDetected));
+    assert_eq!(events.next(), None);
+
+    assert_eq!(
+        mpr121_sensor
+            .poll_events()
+            .expect("Communication should not fail")
+            .len(),
+        0
+    );
+}
+
+#[cfg(feature = "async")]
+mod asynch {
+    use embedded_hal::digital::{ErrorKind, ErrorType};
+    use embedded_hal_async::digital::Wait;
+    use mpr121_hal::events::TouchEvent;
+    use mpr121_hal::mpr121::asynch::Mpr121;
+    use mpr121_hal::sim::Mpr121Sim;
+    use mpr121_hal::{Channel, Electrode, Mpr121Address, Mpr121Error};
+
+    struct NoDelay;
+
+    impl embedded_hal_async::delay::DelayNs for NoDelay {
+        async fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    /// The active low IRQ pin of the simulated device. The line can not change while a test awaits it, so every wait that
+    /// would never finish returns an error instead of blocking the test.
+    struct SimIrq<'a>(&'a Mpr121Sim);
+
+    impl SimIrq<'_> {
+        fn wait_for_level(&self, low: bool) -> Result<(), ErrorKind> {
+            if self.0.is_irq_asserted() == low {
+                Ok(())
+            } else {
+                Err(ErrorKind::Other)
+            }
+        }
+    }
+
+    impl ErrorType for SimIrq<'_> {
+        type Error = ErrorKind;
+    }
+
+    impl Wait for SimIrq<'_> {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(false)
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(true)
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            Err(ErrorKind::Other)
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            Err(ErrorKind::Other)
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            Err(ErrorKind::Other)
+        }
+    }
+
+    #[test]
+    fn test_wait_for_change() {
+        embassy_futures::block_on(async {
+            let sim = Mpr121Sim::new(Mpr121Address::Default);
+            let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+                .await
+                .expect("Sensor Initialisation should not fail");
+            sim.set_touched(Electrode::Channel(Channel::Four), true);
+            let mut events = mpr121_sensor
+                .wait_for_change(&mut SimIrq(&sim))
+                .await
+                .expect("Communication should not fail");
+            assert_eq!(events.next(), Some(TouchEvent::Touched(Channel::Four)));
+            assert_eq!(events.next(), None);
+            // Reading the touch status releases the IRQ pin
+            assert!(!sim.is_irq_asserted());
+        });
+    }
+
+    #[test]
+    fn test_wait_for_change_irq_error() {
+        embassy_futures::block_on(async {
+            let sim = Mpr121Sim::new(Mpr121Address::Default);
+            let mut mpr121_sensor = Mpr121::new(&sim, Mpr121Address::Default, &mut NoDelay)
+                .await
+                .expect("Sensor Initialisation should not fail");
+            // Nothing changed, so the IRQ pin stays high
+            assert!(matches!(
+                mpr121_sensor.wait_for_change(&mut SimIrq(&sim)).await,
+                Err(Mpr121Error::IrqPinError(ErrorKind::Other))
+            ));
+        });
+    }
+}